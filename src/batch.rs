@@ -0,0 +1,323 @@
+use crate::{
+    command::Command,
+    get_secret_number, handle_guess,
+    integer::{Integer, Primitive},
+    interval::KnownInterval,
+    prompt::Prompter,
+    theme::Role,
+    GameOutcome, Guess, GuessCount, GuessError, GuessResult, Incrementable, Parsable,
+};
+use std::io::{BufRead, Write};
+
+/// The guesses a player submits together in one turn of batch mode.
+///
+/// A batch is written as a list of guesses separated by commas, like `25, 50, 75`. When the
+/// list has no comma, spaces separate the guesses instead, so `25 50 75` works too; numbers
+/// written in words with spaces, like "forty two", need commas between them.
+///
+/// # Fields
+/// - `guesses`: The guesses, in the order they were typed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch<T = u32> {
+    guesses: Vec<Guess<T>>,
+}
+
+impl<T: Integer> Batch<T> {
+    /// Creates a batch of the given guesses.
+    pub fn new(guesses: Vec<Guess<T>>) -> Batch<T> {
+        Batch { guesses }
+    }
+
+    /// Returns the guesses, in the order they were typed.
+    pub fn guesses(&self) -> &[Guess<T>] {
+        &self.guesses
+    }
+
+    /// Parses a list of at most `limit` guesses in the range `min..=max`.
+    ///
+    /// # Arguments
+    /// * `input` - The user input to parse.
+    /// * `min`, `max` - The smallest and largest valid guesses.
+    /// * `limit` - The largest number of guesses the batch may have.
+    ///
+    /// # Returns
+    /// Returns a `Result<Batch<T>, GuessError<T>>`:
+    /// - `Ok(Batch)` with between 1 and `limit` guesses.
+    /// - `Err(GuessError::Empty)` if the input or one of the guesses between commas is blank.
+    /// - `Err(GuessError::TooManyGuesses)` if the list has more than `limit` guesses.
+    /// - Any error of `Guess::parse_in_range` for a guess that is not a number in the range.
+    pub fn parse(input: &str, min: T, max: T, limit: usize) -> Result<Batch<T>, GuessError<T>> {
+        let input = input.trim();
+        let parts: Vec<&str> = if input.contains(',') {
+            input.split(',').collect()
+        } else {
+            input.split_whitespace().collect()
+        };
+        if parts.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        if parts.len() > limit {
+            return Err(GuessError::TooManyGuesses {
+                input: input.to_string(),
+                limit,
+            });
+        }
+        let guesses = parts
+            .into_iter()
+            .map(|part| Guess::parse_in_range(part, min.clone(), max.clone(), None))
+            .collect::<Result<_, _>>()?;
+        Ok(Batch { guesses })
+    }
+}
+
+/// Trait implementation for `Batch` to make it parsable from a string input.
+///
+/// The input is parsed by `Batch::parse` with the range of the classic game, 1 to 100, and
+/// no limit on the number of guesses.
+impl<T: Primitive> Parsable for Batch<T> {
+    type Value = T;
+
+    fn parse_input(input: &str) -> Result<Batch<T>, GuessError<T>> {
+        Batch::parse(input, T::DEFAULT_MIN, T::DEFAULT_MAX, usize::MAX)
+    }
+}
+
+/// The feedback for one turn of batch mode.
+///
+/// # Fields
+/// - `results`: Each guess of the batch with its result, in the order they were typed.
+/// - `found`: Whether one of the guesses was the secret number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Turn<T = u32> {
+    pub results: Vec<(T, GuessResult)>,
+    pub found: bool,
+}
+
+/// Compares every guess of a batch with the secret number and gathers the results into one
+/// turn.
+///
+/// This is `handle_guess` for a whole batch. The results are also recorded in `known`, so it
+/// holds what the player knows about the secret number after the turn, whichever guesses of
+/// the batch narrowed it down.
+///
+/// # Arguments
+/// * `batch` - The guesses of the turn.
+/// * `secret` - The secret number.
+/// * `known` - What the feedback has shown so far, updated with the results of the turn.
+pub fn handle_batch<T: Integer>(
+    batch: &Batch<T>,
+    secret: &Guess<T>,
+    known: &mut KnownInterval<T>,
+) -> Turn<T> {
+    let results: Vec<(T, GuessResult)> = batch
+        .guesses
+        .iter()
+        .map(|guess| (guess.value(), handle_guess(guess.clone(), secret)))
+        .collect();
+    for (value, result) in &results {
+        known.record(value.clone(), *result);
+    }
+    let found = results
+        .iter()
+        .any(|(_, result)| *result == GuessResult::Correct);
+    Turn { results, found }
+}
+
+/// Enum to represent one line of input from the player in batch mode.
+///
+/// # Variants
+///
+/// - `Batch(Batch<T>)`: The player submitted the guesses of a turn.
+/// - `Command(Command)`: The player typed a command such as `:q`.
+#[derive(Debug, PartialEq)]
+pub enum BatchInput<T = u32> {
+    Batch(Batch<T>),
+    Command(Command),
+}
+
+/// Parses a line typed by the player into a command or a batch of at most `limit` guesses in
+/// the range `min..=max`.
+///
+/// Lines starting with a colon are commands (see `Command::parse`); anything else is parsed
+/// by `Batch::parse`.
+pub fn parse_batch_input<T: Integer>(
+    line: &str,
+    min: T,
+    max: T,
+    limit: usize,
+) -> Result<BatchInput<T>, GuessError<T>> {
+    if let Some(command) = Command::parse(line) {
+        return command.map(BatchInput::Command);
+    }
+
+    Batch::parse(line, min, max, limit).map(BatchInput::Batch)
+}
+
+/// Plays one game in batches of guesses and returns how it ended.
+///
+/// Each turn, the player submits up to `size` guesses at once, as in a parallel search, and
+/// gets the result of each from `handle_batch`, followed by the range the secret number
+/// must be in after the whole turn. The score is the number of turns, counted by a
+/// `GuessCount`, and the total number of guesses is shown too. Only the `:q` command is
+/// available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `size` - The largest number of guesses in a turn.
+/// * `min`, `max` - The smallest and largest possible secret number.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    size: usize,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    prompter.say(&ui.plural(Role::Info, "batch-intro", size as u64, &[]));
+
+    let secret = get_secret_number(min.clone(), max.clone());
+    let secret_number = Guess::in_range(secret, min.clone(), max.clone())
+        .expect("Failed to generate secret number");
+    let mut known = KnownInterval::new(min.clone(), max.clone());
+    let mut turn_count = GuessCount::new();
+    let mut guess_count = GuessCount::new();
+
+    let outcome = loop {
+        let input =
+            match prompter.next(|line| parse_batch_input(line, min.clone(), max.clone(), size)) {
+                Ok(input) => input,
+                Err(outcome) => break outcome,
+            };
+
+        let guesses = match input {
+            BatchInput::Batch(guesses) => guesses,
+            BatchInput::Command(Command::Quit) => break GameOutcome::Quit,
+            BatchInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+
+        turn_count.increment();
+        for _ in guesses.guesses() {
+            guess_count.increment();
+        }
+        let turn = handle_batch(&guesses, &secret_number, &mut known);
+        let parts: Vec<String> = turn
+            .results
+            .iter()
+            .map(|(value, result)| {
+                let result = ui.result_text(*result);
+                ui.catalog
+                    .format("batch-result", &[("value", value), ("result", &result)])
+            })
+            .collect();
+        prompter.say(&parts.join(", "));
+
+        if turn.found {
+            let turns = u64::from(turn_count.value());
+            let guesses = u64::from(guess_count.value());
+            prompter.say(&ui.plural(Role::Correct, "batch-win", turns, &[]));
+            prompter.say(&ui.plural(Role::Info, "batch-guesses", guesses, &[]));
+            break GameOutcome::Won;
+        }
+        let (low, high) = known.bounds();
+        prompter.say(&ui.format(Role::Info, "range", &[("min", &low), ("max", &high)]));
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(turn_count.value());
+        prompter.say(&ui.plural(
+            Role::Headline,
+            "reveal-batch",
+            count,
+            &[("secret", &secret_number.value())],
+        ));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(input: &str) -> Vec<i64> {
+        Batch::parse(input, 1, 100, 3)
+            .unwrap()
+            .guesses()
+            .iter()
+            .map(Guess::value)
+            .collect()
+    }
+
+    // Test parsing lists of guesses
+    #[test]
+    fn parse_batches() {
+        assert_eq!(batch("25, 50, 75"), [25, 50, 75]);
+        assert_eq!(batch("  25 50\t75 "), [25, 50, 75]);
+        assert_eq!(batch("forty two, 7"), [42, 7]);
+        assert_eq!(batch("60"), [60]);
+        assert_eq!(
+            Batch::<i64>::parse("1 2 3 4", 1, 100, 3),
+            Err(GuessError::TooManyGuesses {
+                input: "1 2 3 4".to_string(),
+                limit: 3
+            })
+        );
+        assert_eq!(Batch::<i64>::parse("  ", 1, 100, 3), Err(GuessError::Empty));
+        assert_eq!(
+            Batch::<i64>::parse("5,,7", 1, 100, 3),
+            Err(GuessError::Empty)
+        );
+        assert_eq!(
+            Batch::<u32>::parse_input("10, 200"),
+            Err(GuessError::OutOfRange {
+                value: 200,
+                min: 1,
+                max: 100
+            })
+        );
+    }
+
+    // Test the feedback for a whole turn
+    #[test]
+    fn turn_feedback() {
+        let secret = Guess::in_range(40, 1, 100).unwrap();
+        let mut known = KnownInterval::new(1, 100);
+        let turn = handle_batch(
+            &Batch::parse("25, 50, 75", 1, 100, 3).unwrap(),
+            &secret,
+            &mut known,
+        );
+        assert_eq!(
+            turn.results,
+            [
+                (25, GuessResult::TooSmall),
+                (50, GuessResult::TooBig),
+                (75, GuessResult::TooBig)
+            ]
+        );
+        assert!(!turn.found);
+        assert_eq!(known.bounds(), (26, 49));
+
+        let turn = handle_batch(
+            &Batch::parse("30 40", 1, 100, 3).unwrap(),
+            &secret,
+            &mut known,
+        );
+        assert!(turn.found);
+    }
+
+    // Test reading batches and commands
+    #[test]
+    fn parse_input() {
+        assert_eq!(
+            parse_batch_input::<i64>(":q", 1, 100, 2),
+            Ok(BatchInput::Command(Command::Quit))
+        );
+        assert!(matches!(
+            parse_batch_input::<i64>("1, 2", 1, 100, 2),
+            Ok(BatchInput::Batch(_))
+        ));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    command::Command, get_secret_number, handle_guess, integer::Integer, parse_player_input,
+    prompt::Prompter, theme::Role, GameOutcome, GuessCount, GuessResult, Guessable, Incrementable,
+    PlayerInput,
+};
+use num_bigint::BigUint;
+use std::io::{BufRead, Write};
+
+/// Enum to represent which way round a circle of numbers is shorter from a guess to the secret.
+///
+/// # Variants
+///
+/// - `Here`: The guess is the secret.
+/// - `Clockwise`: Counting up from the guess, and wrapping from the largest number to the
+///   smallest, reaches the secret sooner.
+/// - `CounterClockwise`: Counting down from the guess, and wrapping from the smallest number
+///   to the largest, reaches the secret sooner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Here,
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Converts a direction into the `GuessResult` of a guess compared with the secret: going
+/// clockwise counts up, as after a guess that is too small, and going counter-clockwise
+/// counts down, as after a guess that is too big.
+impl From<Direction> for GuessResult {
+    fn from(direction: Direction) -> GuessResult {
+        match direction {
+            Direction::Here => GuessResult::Correct,
+            Direction::Clockwise => GuessResult::TooSmall,
+            Direction::CounterClockwise => GuessResult::TooBig,
+        }
+    }
+}
+
+/// A range of numbers that wraps around like a clock face, so the largest number is followed
+/// by the smallest.
+///
+/// On a circle from 0 to 59, 55 is 10 steps clockwise from 5 and 50 steps counter-clockwise,
+/// so there is no "too small" or "too big", only the shorter way round.
+///
+/// # Fields
+/// - `min`, `max`: The smallest and largest number on the circle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle<T = u32> {
+    min: T,
+    max: T,
+}
+
+impl<T: Integer> Circle<T> {
+    /// Creates the circle of the numbers from `min` to `max`.
+    pub fn new(min: T, max: T) -> Circle<T> {
+        Circle { min, max }
+    }
+
+    /// Returns the smallest and largest number on the circle.
+    pub fn range(&self) -> (T, T) {
+        (self.min.clone(), self.max.clone())
+    }
+
+    /// Returns how many numbers are on the circle.
+    pub fn size(&self) -> BigUint {
+        T::distance(&self.min, &self.max) + 1u32
+    }
+
+    /// Returns the number of steps from `from` to `to` going clockwise.
+    pub fn clockwise_steps(&self, from: &T, to: &T) -> BigUint {
+        if from <= to {
+            T::distance(from, to)
+        } else {
+            self.size() - T::distance(to, from)
+        }
+    }
+
+    /// Returns the number of steps between `a` and `b` going the shorter way round.
+    pub fn steps(&self, a: &T, b: &T) -> BigUint {
+        let clockwise = self.clockwise_steps(a, b);
+        let counter = self.clockwise_steps(b, a);
+        clockwise.min(counter)
+    }
+
+    /// Returns the shorter way round from `guess` to `secret`.
+    ///
+    /// When both ways are equally long, which happens when the secret is exactly opposite the
+    /// guess on a circle with an even number of numbers, the direction is `Clockwise`.
+    pub fn direction(&self, guess: &T, secret: &T) -> Direction {
+        if guess == secret {
+            return Direction::Here;
+        }
+        let clockwise = self.clockwise_steps(guess, secret);
+        let counter = self.clockwise_steps(secret, guess);
+        if clockwise <= counter {
+            Direction::Clockwise
+        } else {
+            Direction::CounterClockwise
+        }
+    }
+
+    /// Places `value` on the circle, so it can be compared with `handle_guess`.
+    pub fn position(&self, value: T) -> Position<T> {
+        Position {
+            value,
+            circle: self.clone(),
+        }
+    }
+}
+
+/// A number on a circle, as guessed by the player or hidden as the secret.
+///
+/// # Fields
+/// - `value`: The number.
+/// - `circle`: The circle the number is on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position<T = u32> {
+    value: T,
+    circle: Circle<T>,
+}
+
+impl<T: Integer> Position<T> {
+    /// Returns the number.
+    pub fn value(&self) -> T {
+        self.value.clone()
+    }
+}
+
+/// Trait implementation for `Position` to make it "guessable".
+///
+/// A circle has no order, so the comparison is the `Direction` from the guess to the secret
+/// turned into a `GuessResult`: `handle_guess` then reports `GuessResult::TooSmall` when the
+/// shorter way is clockwise and `GuessResult::TooBig` when it is counter-clockwise.
+impl<T: Integer> Guessable for Position<T> {
+    type Feedback = GuessResult;
+
+    fn compare(&self, other: &Self) -> GuessResult {
+        self.circle.direction(&self.value, &other.value).into()
+    }
+}
+
+/// Plays one game on a circle of numbers and returns how it ended.
+///
+/// The range wraps around like a clock face, so after each guess the player is told the
+/// shorter way round to the secret number, found by comparing their `Position`s on the
+/// `Circle` with `handle_guess`: too small means clockwise and too big means
+/// counter-clockwise. Guesses can use `last` for the previous guess. Only the `:q` command is
+/// available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `min`, `max` - The smallest and largest number on the circle.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    prompter.say(&ui.format(Role::Info, "circle-intro", &[("min", &min), ("max", &max)]));
+
+    let circle = Circle::new(min.clone(), max.clone());
+    let secret = circle.position(get_secret_number(min.clone(), max.clone()));
+    let mut guess_count = GuessCount::new();
+    let mut last: Option<T> = None;
+
+    let outcome = loop {
+        let input = match prompter
+            .next(|line| parse_player_input(line, min.clone(), max.clone(), last.clone()))
+        {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let guess = match input {
+            PlayerInput::Guess(guess) => guess,
+            PlayerInput::Command(Command::Quit) => break GameOutcome::Quit,
+            PlayerInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+
+        guess_count.increment();
+        match handle_guess(circle.position(guess.value()), &secret) {
+            GuessResult::Correct => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                break GameOutcome::Won;
+            }
+            GuessResult::TooSmall => prompter.say(&ui.text(Role::TooSmall, "circle-clockwise")),
+            GuessResult::TooBig => prompter.say(&ui.text(Role::TooBig, "circle-counter-clockwise")),
+        }
+        last = Some(guess.value());
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(
+            Role::Headline,
+            "reveal",
+            count,
+            &[("secret", &secret.value())],
+        ));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{handle_guess, GuessResult};
+    use num_bigint::BigInt;
+
+    // Test the shorter way round a clock face
+    #[test]
+    fn clock_directions() {
+        let clock = Circle::new(0i64, 59);
+        assert_eq!(clock.size(), BigUint::from(60u32));
+        assert_eq!(clock.direction(&10, &20), Direction::Clockwise);
+        assert_eq!(clock.direction(&20, &10), Direction::CounterClockwise);
+        assert_eq!(clock.direction(&55, &5), Direction::Clockwise);
+        assert_eq!(clock.direction(&5, &55), Direction::CounterClockwise);
+        assert_eq!(clock.direction(&0, &30), Direction::Clockwise);
+        assert_eq!(clock.direction(&42, &42), Direction::Here);
+        assert_eq!(clock.clockwise_steps(&55, &5), BigUint::from(10u32));
+        assert_eq!(clock.steps(&5, &55), BigUint::from(10u32));
+        assert_eq!(clock.steps(&0, &30), BigUint::from(30u32));
+    }
+
+    // Test that guesses on a circle are compared by direction
+    #[test]
+    fn guess_on_circle() {
+        let circle = Circle::new(1i64, 12);
+        let secret = circle.position(1);
+        assert_eq!(
+            handle_guess(circle.position(11), &secret),
+            GuessResult::TooSmall
+        );
+        assert_eq!(
+            handle_guess(circle.position(3), &secret),
+            GuessResult::TooBig
+        );
+        assert_eq!(
+            handle_guess(circle.position(1), &secret),
+            GuessResult::Correct
+        );
+    }
+
+    // Test circles of big integers and of whole types
+    #[test]
+    fn large_circles() {
+        let max = BigInt::from(10).pow(30u32);
+        let circle = Circle::new(BigInt::from(0), max.clone());
+        assert_eq!(
+            circle.direction(&max, &BigInt::from(5)),
+            Direction::Clockwise
+        );
+        let bytes = Circle::new(0u8, 255);
+        assert_eq!(bytes.size(), BigUint::from(256u32));
+        assert_eq!(bytes.direction(&250, &3), Direction::Clockwise);
+        assert_eq!(bytes.direction(&3, &250), Direction::CounterClockwise);
+    }
+}
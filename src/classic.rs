@@ -0,0 +1,248 @@
+use crate::{
+    command::Command, get_secret_number, handle_guess, history::History, integer::Integer,
+    interval::WastedGuess, parse_player_input, prompt::Prompter, theme::Role, ui::Ui, GameOutcome,
+    Guess, GuessCount, GuessResult, Incrementable, PlayerInput,
+};
+use std::io::{BufRead, Write};
+
+/// Plays one game of the classic guessing game over the range `min..=max` and returns how it
+/// ended.
+///
+/// It works with any integer type, so the same game can be played with `i64` numbers or with
+/// `BigInt`s.
+///
+/// # Flow
+/// 1. The game generates a random secret number in the range using the `get_secret_number`
+///    function.
+/// 2. It initializes a `GuessCount` to track the number of guesses the player has made.
+/// 3. The game enters a loop where it:
+///    - Prompts the user to input a guess using the `Prompter`.
+///    - Validates and parses the input with `parse_player_input`, handling any errors (e.g.,
+///      invalid input or out-of-range guesses).
+///    - Runs commands such as `:hint` or `:history` with `run_command`, without counting them
+///      as guesses.
+///    - Warns about wasted guesses, which are repeated or outside the range earlier feedback
+///      allows, and does not count them if `skip_wasted` is set.
+///    - Compares the guess to the secret number using the `handle_guess` function.
+///    - Increments the guess count with each attempt.
+/// 4. The loop continues until the user guesses correctly, at which point a success message
+///    is printed and the game ends.
+/// 5. If the player quits with `:q`, the input ends, cannot be read, or too many invalid
+///    guesses are made in a row, the game stops early and reveals the secret number.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `skip_wasted` - Whether wasted guesses are left out of the count, as chosen with
+///   `--skip-wasted`.
+/// * `min`, `max` - The smallest and largest possible secret number.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    skip_wasted: bool,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+
+    // Generate a random secret number between min and max. we use the Guess for the secret
+    // number to make it easier to compare the users input Guess against the secret number
+    // using the `.compare` trait in the `handle_guess` method.
+    let secret = get_secret_number(min.clone(), max.clone());
+    let secret_number = Guess::in_range(secret, min.clone(), max.clone())
+        .expect("Failed to generate secret number");
+
+    // Initialize the guess count to track the number of attempts.
+    let mut guess_count = GuessCount::new();
+
+    // Record every guess and its result, for commands like `:history` and expressions
+    // like `last+10`.
+    let mut history = History::new(min.clone(), max.clone());
+
+    // Game loop: continue until the user guesses correctly or no more guesses can be read.
+    let outcome = loop {
+        // Get the user's guess or command; errors are reported by the prompter.
+        let last = history.last_value();
+        let input = match prompter
+            .next(|line| parse_player_input(line, min.clone(), max.clone(), last.clone()))
+        {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        // Run commands without counting them as guesses.
+        let guess = match input {
+            PlayerInput::Guess(guess) => guess,
+            PlayerInput::Command(Command::Quit) => break GameOutcome::Quit,
+            PlayerInput::Command(command) => {
+                run_command(prompter, command, &mut history, &guess_count);
+                continue;
+            }
+        };
+        let value = guess.value();
+
+        // Warn about guesses whose answer the player already knows, and leave them out of
+        // the count if asked to.
+        let wasted = history.interval().check(value.clone());
+        if let Some(wasted) = &wasted {
+            prompter.say(&wasted_message(ui, &value, wasted));
+        }
+
+        // Increment the guess count after each guess.
+        if wasted.is_none() || !skip_wasted {
+            guess_count.increment();
+        }
+
+        // Compare the guess to the secret number and check if the user wins.
+        let result = handle_guess(guess, &secret_number);
+        history.record(value, result);
+        match result {
+            GuessResult::TooSmall => prompter.say(&ui.text(Role::TooSmall, "too-small")),
+            GuessResult::TooBig => prompter.say(&ui.text(Role::TooBig, "too-big")),
+            GuessResult::Correct => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                break GameOutcome::Won;
+            }
+        }
+    };
+
+    // Reveal the secret number when the game ended without a win.
+    if outcome != GameOutcome::Won {
+        let secret = secret_number.value();
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(Role::Headline, "reveal", count, &[("secret", &secret)]));
+    }
+    outcome
+}
+
+/// Returns the warning for a wasted guess.
+///
+/// # Arguments
+/// * `ui` - The `Ui` used to translate and style the warning.
+/// * `value` - The value that was guessed.
+/// * `wasted` - Why the guess is wasted.
+fn wasted_message<T: Integer>(ui: &Ui, value: &T, wasted: &WastedGuess<T>) -> String {
+    match wasted {
+        WastedGuess::Repeated => ui.format(Role::Error, "wasted-repeated", &[("value", value)]),
+        WastedGuess::AlreadyAbove { bound } => {
+            ui.format(Role::Error, "wasted-above", &[("bound", bound)])
+        }
+        WastedGuess::AlreadyBelow { bound } => {
+            ui.format(Role::Error, "wasted-below", &[("bound", bound)])
+        }
+    }
+}
+
+/// Runs an in-game command and prints its output.
+///
+/// # Arguments
+/// * `prompter` - Prints the output, translated and styled by its `Ui`.
+/// * `command` - The command to run.
+/// * `history` - The guesses made so far, which `:undo` may change.
+/// * `guess_count` - The number of guesses made so far, shown by `:stats`.
+///
+/// # Panics
+/// Panics on `Command::Quit`, which the game loop handles itself by ending the game.
+fn run_command<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    command: Command,
+    history: &mut History<T>,
+    guess_count: &GuessCount,
+) {
+    let ui = prompter.ui();
+    let (min, max) = history.known_range();
+    match command {
+        Command::Quit => unreachable!("the game loop ends the game on :q instead of running it"),
+        Command::Hint => {
+            let value = history.hint();
+            prompter.say(&ui.format(
+                Role::Info,
+                "hint",
+                &[("value", &value), ("min", &min), ("max", &max)],
+            ));
+        }
+        Command::History => {
+            if history.entries().is_empty() {
+                prompter.say(&ui.text(Role::Info, "history-empty"));
+            }
+            // Only the result is styled, since styles cannot be nested.
+            for (index, (value, result)) in history.entries().iter().enumerate() {
+                let result = ui.result_text(*result);
+                let index = index + 1;
+                prompter.say(&ui.catalog.format(
+                    "history-entry",
+                    &[("index", &index), ("value", value), ("result", &result)],
+                ));
+            }
+        }
+        Command::Range => {
+            prompter.say(&ui.format(Role::Info, "range", &[("min", &min), ("max", &max)]));
+        }
+        Command::Undo => match history.undo() {
+            Some((value, _)) => prompter.say(&ui.format(Role::Info, "undo", &[("value", &value)])),
+            None => prompter.say(&ui.text(Role::Info, "undo-empty")),
+        },
+        Command::Stats => {
+            let count = u64::from(guess_count.value());
+            let left = history.interval().remaining();
+            prompter.say(&ui.plural(Role::Info, "stats", count, &[("left", &left)]));
+        }
+        Command::Help => {
+            prompter.say(&ui.text(Role::Info, "help"));
+            for command in Command::ALL {
+                let name = format!(":{}", command.names()[0]);
+                prompter.say(&format!(
+                    "  {:<10} {}",
+                    name,
+                    ui.catalog.get(command.help_key())
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{i18n::Locale, theme::Theme};
+    use std::io;
+
+    // Test playing whole games from scripted input
+    #[test]
+    fn play_scripted_games() {
+        let ui = Ui::new(Theme::plain(), Locale::English);
+        let mut out = Vec::new();
+        let mut prompter = Prompter::new(&ui, io::Cursor::new(":range\n7\n"), &mut out);
+        assert_eq!(play(&mut prompter, false, 7i64, 7), GameOutcome::Won);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("between 7 and 7"));
+        assert!(out.ends_with("You win, in 1 guess!\n"));
+
+        let mut out = Vec::new();
+        let mut prompter = Prompter::new(&ui, io::Cursor::new(":q\n"), &mut out);
+        assert_eq!(play(&mut prompter, false, 7i64, 7), GameOutcome::Quit);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("The secret number was 7. You used 0 guesses.\n"));
+    }
+
+    // Test that only the result of a history entry is styled
+    #[test]
+    fn history_styles_results() {
+        let ui = Ui::new(Theme::classic(), Locale::English);
+        let mut history = History::new(1i64, 100);
+        history.record(30, GuessResult::TooSmall);
+        let mut out = Vec::new();
+        let mut prompter = Prompter::new(&ui, io::empty(), &mut out);
+        run_command(
+            &mut prompter,
+            Command::History,
+            &mut history,
+            &GuessCount::new(),
+        );
+        let too_small = ui.text(Role::TooSmall, "too-small");
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("1. 30: {}\n", too_small)
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::GuessError;
+
+/// Enum to represent the commands a player can type instead of a guess.
+///
+/// Commands start with a colon, so they can never be mistaken for a guess. They let the
+/// player inspect and control the game in the middle of a round, and they are not counted
+/// as guesses.
+///
+/// # Variants
+///
+/// - `Quit` (`:q`, `:quit`): Ends the game and reveals the secret number.
+/// - `Hint` (`:hint`): Suggests the next guess.
+/// - `History` (`:history`): Lists the guesses made so far and their results.
+/// - `Range` (`:range`): Shows the range the secret number must be in.
+/// - `Undo` (`:undo`): Forgets the last guess.
+/// - `Stats` (`:stats`): Shows how many guesses were made and how many numbers are left.
+/// - `Help` (`:help`, `:h`, `:?`): Lists the commands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    Quit,
+    Hint,
+    History,
+    Range,
+    Undo,
+    Stats,
+    Help,
+}
+
+impl Command {
+    /// Every command, in the order they are listed by `:help`.
+    pub const ALL: [Command; 7] = [
+        Command::Quit,
+        Command::Hint,
+        Command::History,
+        Command::Range,
+        Command::Undo,
+        Command::Stats,
+        Command::Help,
+    ];
+
+    /// Returns the names the command can be typed as, without the colon.
+    ///
+    /// The first name is the one shown by `:help`.
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            Command::Quit => &["q", "quit"],
+            Command::Hint => &["hint"],
+            Command::History => &["history"],
+            Command::Range => &["range"],
+            Command::Undo => &["undo"],
+            Command::Stats => &["stats"],
+            Command::Help => &["help", "h", "?"],
+        }
+    }
+
+    /// Returns the catalog key of the command's description in `:help`.
+    pub fn help_key(&self) -> &'static str {
+        match self {
+            Command::Quit => "help-quit",
+            Command::Hint => "help-hint",
+            Command::History => "help-history",
+            Command::Range => "help-range",
+            Command::Undo => "help-undo",
+            Command::Stats => "help-stats",
+            Command::Help => "help-help",
+        }
+    }
+
+    /// Parses a line of input as a command.
+    ///
+    /// # Arguments
+    /// * `input` - The line typed by the player.
+    ///
+    /// # Returns
+    /// Returns an `Option`:
+    /// - `None` if the input does not start with a colon, so it is not a command at all.
+    /// - `Some(Ok(Command))` if it names a known command, ignoring case.
+    /// - `Some(Err(GuessError::UnknownCommand))` if it starts with a colon but names no command.
+    pub fn parse<T>(input: &str) -> Option<Result<Command, GuessError<T>>> {
+        let input = input.trim();
+        let name = input.strip_prefix(':')?.trim().to_ascii_lowercase();
+        let command = Command::ALL
+            .into_iter()
+            .find(|command| command.names().contains(&name.as_str()));
+        Some(command.ok_or_else(|| GuessError::UnknownCommand {
+            input: input.to_string(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that every command can be typed by each of its names
+    #[test]
+    fn parse_every_name() {
+        for command in Command::ALL {
+            for name in command.names() {
+                let input = format!(":{}\n", name);
+                assert_eq!(Command::parse::<u32>(&input), Some(Ok(command)));
+            }
+        }
+        assert_eq!(Command::parse::<u32>(" :HINT "), Some(Ok(Command::Hint)));
+    }
+
+    // Test that guesses are not commands and unknown commands are errors
+    #[test]
+    fn parse_non_commands() {
+        assert_eq!(Command::parse::<u32>("42"), None);
+        assert_eq!(Command::parse::<u32>("quit"), None);
+        assert_eq!(
+            Command::parse::<u32>(":dance"),
+            Some(Err(GuessError::UnknownCommand {
+                input: ":dance".to_string()
+            }))
+        );
+    }
+}
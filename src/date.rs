@@ -0,0 +1,580 @@
+use crate::{
+    command::Command, dictionary::fold, get_secret_number, handle_guess, prompt::Prompter,
+    theme::Role, GameOutcome, GuessCount, GuessError, GuessResult, Guessable, Incrementable,
+    InputKind, Parsable,
+};
+use std::{
+    fmt,
+    io::{BufRead, Write},
+};
+
+/// The names months can be typed as, in every supported language, with January first.
+///
+/// Names are compared after `dictionary::fold`, so case and accents do not matter.
+const MONTH_NAMES: [&[&str]; 12] = [
+    &["january", "jan", "enero", "ene", "janvier", "janv"],
+    &["february", "feb", "febrero", "fevrier", "fevr", "fev"],
+    &["march", "mar", "marzo", "mars"],
+    &["april", "apr", "abril", "abr", "avril", "avr"],
+    &["may", "mayo", "mai"],
+    &["june", "jun", "junio", "juin"],
+    &["july", "jul", "julio", "juillet", "juil"],
+    &["august", "aug", "agosto", "ago", "aout"],
+    &["september", "sep", "sept", "septiembre", "septembre"],
+    &["october", "oct", "octubre", "octobre"],
+    &["november", "nov", "noviembre", "novembre"],
+    &["december", "dec", "diciembre", "dic", "decembre"],
+];
+
+/// Small words that can appear in a written date and carry no meaning, as in
+/// "15 de marzo de 2024" or "the 15th of March".
+const FILLER_WORDS: [&str; 4] = ["de", "of", "the", "le"];
+
+/// A day of the calendar, such as 2024-03-15.
+///
+/// The calendar is the proleptic Gregorian calendar, and years go from 1 to 9999. Dates are
+/// ordered from earliest to latest.
+///
+/// # Fields
+/// - `year`: The year, from 1 to 9999.
+/// - `month`: The month, from 1 for January to 12 for December.
+/// - `day`: The day of the month, from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+/// Enum to represent how much of a guessed date matches the secret date.
+///
+/// # Variants
+///
+/// - `Nothing`: The years differ.
+/// - `Year`: The year is right, but not the month.
+/// - `Month`: The year and the month are right, but not the day.
+/// - `Day`: The dates are the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateMatch {
+    Nothing,
+    Year,
+    Month,
+    Day,
+}
+
+impl Date {
+    /// Creates a date, or returns `None` if there is no such day, e.g. February 30.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        let valid = (1..=9999).contains(&year)
+            && (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day);
+        valid.then_some(Date { year, month, day })
+    }
+
+    /// Returns the year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the month, from 1 for January to 12 for December.
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Returns the day of the month.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Returns the number of days since 1970-01-01, negative for earlier dates.
+    pub fn to_days(&self) -> i64 {
+        // The year is counted from March, so the leap day comes last.
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Returns the date `days` days after 1970-01-01, or `None` if its year is not from 1
+    /// to 9999.
+    pub fn from_days(days: i64) -> Option<Date> {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date::new(
+            i32::try_from(year).ok()?,
+            u32::try_from(month).ok()?,
+            u32::try_from(day).ok()?,
+        )
+    }
+
+    /// Tells how much of this date matches `other`: the year, the year and month, or all.
+    pub fn matching(&self, other: &Date) -> DateMatch {
+        match (self.year == other.year, self.month == other.month) {
+            _ if self == other => DateMatch::Day,
+            (true, true) => DateMatch::Month,
+            (true, false) => DateMatch::Year,
+            (false, _) => DateMatch::Nothing,
+        }
+    }
+
+    /// Parses a date typed in one of the common formats.
+    ///
+    /// The accepted formats are:
+    /// - ISO dates such as `2024-03-15`, also with `/` or `.` as separators.
+    /// - Numeric dates with the year last, such as `15/03/2024` or `03/15/2024`. When the
+    ///   day and the month could be swapped, `month_first` decides which comes first.
+    /// - Dates with the month written out in any supported language, such as
+    ///   `March 15, 2024`, `15th of March 2024`, `15 de marzo de 2024` or `15 mars 2024`.
+    ///
+    /// # Returns
+    /// Returns `Some(Date)` if the input is a valid date in one of these formats, and `None`
+    /// otherwise, including for days that do not exist such as `2023-02-29`.
+    pub fn parse(input: &str, month_first: bool) -> Option<Date> {
+        let text = fold(input);
+        if let Some(date) = Date::parse_numeric(&text, month_first) {
+            return Some(date);
+        }
+
+        let tokens: Vec<&str> = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty() && !FILLER_WORDS.contains(token))
+            .collect();
+        let month = tokens
+            .iter()
+            .position(|token| month_number(token).is_some())?;
+        let numbers: Vec<&str> = tokens
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != month)
+            .map(|(_, token)| strip_ordinal(token))
+            .collect();
+        let [first, second] = numbers[..] else {
+            return None;
+        };
+        // The year is the number with more than two digits.
+        let (day, year) = match (first.len() > 2, second.len() > 2) {
+            (false, true) => (first, second),
+            (true, false) => (second, first),
+            _ => return None,
+        };
+        Date::new(
+            year.parse().ok()?,
+            month_number(tokens[month])?,
+            day.parse().ok()?,
+        )
+    }
+
+    /// Parses a date made only of numbers and separators, such as `2024-03-15`.
+    fn parse_numeric(text: &str, month_first: bool) -> Option<Date> {
+        let parts: Vec<&str> = text.split(['-', '/', '.']).collect();
+        let [a, b, c] = parts[..] else {
+            return None;
+        };
+        if !parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return None;
+        }
+        if a.len() == 4 {
+            return Date::new(a.parse().ok()?, b.parse().ok()?, c.parse().ok()?);
+        }
+        if c.len() != 4 {
+            return None;
+        }
+        let (a, b): (u32, u32) = (a.parse().ok()?, b.parse().ok()?);
+        // Only a number above 12 shows for sure which one is the day.
+        let (month, day) = if a > 12 || (b <= 12 && !month_first) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        Date::new(c.parse().ok()?, month, day)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Returns `true` if `year` has a February 29.
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the number of days in a month, from 1 for January to 12 for December.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of the month named `name`, which must already be folded.
+fn month_number(name: &str) -> Option<u32> {
+    let name = name.trim_end_matches('.');
+    MONTH_NAMES
+        .iter()
+        .position(|names| names.contains(&name))
+        .map(|index| index as u32 + 1)
+}
+
+/// Removes an ordinal suffix from a day, as in `15th`, `1st` or `1er`.
+fn strip_ordinal(token: &str) -> &str {
+    ["st", "nd", "rd", "th", "er"]
+        .iter()
+        .find_map(|suffix| {
+            token
+                .strip_suffix(suffix)
+                .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .unwrap_or(token)
+}
+
+/// Trait implementation for `Date` to make it "guessable".
+///
+/// Dates are ordered from earliest to latest, so `handle_guess` tells whether the secret date
+/// is later (`TooSmall`) or earlier (`TooBig`) than the guess.
+impl Guessable for Date {
+    type Feedback = GuessResult;
+
+    fn compare(&self, other: &Self) -> GuessResult {
+        self.cmp(other).into()
+    }
+}
+
+/// Trait implementation for `Date` to make it parsable from a string input.
+///
+/// The input is parsed by `DateGame::parse` for the default game, with dates from
+/// `DateGame::DEFAULT_YEARS` and the day first in ambiguous numeric dates.
+impl Parsable for Date {
+    type Value = Date;
+
+    fn parse_input(input: &str) -> Result<Date, GuessError<Date>> {
+        DateGame::default().parse(input)
+    }
+}
+
+/// Enum to represent one line of input from the player in date mode.
+///
+/// # Variants
+///
+/// - `Guess(Date)`: The player guessed a date.
+/// - `Command(Command)`: The player typed a command such as `:q`.
+#[derive(Debug, PartialEq)]
+pub enum DateInput {
+    Guess(Date),
+    Command(Command),
+}
+
+/// A struct holding the settings of a game played with dates.
+///
+/// # Fields
+/// - `min`, `max`: The earliest and latest possible secret date.
+/// - `hints`: Whether wrong guesses also say if the year or the month is right.
+/// - `month_first`: Whether the month comes first in numeric dates like `03/04/2024`, as in
+///   American English.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateGame {
+    min: Date,
+    max: Date,
+    hints: bool,
+    month_first: bool,
+}
+
+impl Default for DateGame {
+    fn default() -> Self {
+        let (first, last) = DateGame::DEFAULT_YEARS;
+        DateGame::new(first, last, false, false)
+    }
+}
+
+impl DateGame {
+    /// The years the secret date is drawn from unless others are chosen.
+    pub const DEFAULT_YEARS: (i32, i32) = (1950, 2050);
+
+    /// Creates the settings for a game with dates from January 1 of `first_year` to
+    /// December 31 of `last_year`.
+    ///
+    /// # Panics
+    /// Panics if the years are not from 1 to 9999, or `first_year` is after `last_year`.
+    pub fn new(first_year: i32, last_year: i32, hints: bool, month_first: bool) -> DateGame {
+        assert!(
+            first_year <= last_year,
+            "the first year comes after the last"
+        );
+        DateGame {
+            min: Date::new(first_year, 1, 1).expect("the first year is from 1 to 9999"),
+            max: Date::new(last_year, 12, 31).expect("the last year is from 1 to 9999"),
+            hints,
+            month_first,
+        }
+    }
+
+    /// Returns the earliest and latest possible secret date.
+    pub fn range(&self) -> (Date, Date) {
+        (self.min, self.max)
+    }
+
+    /// Returns whether wrong guesses also say if the year or the month is right.
+    pub fn hints(&self) -> bool {
+        self.hints
+    }
+
+    /// Draws a random secret date, with every day equally likely.
+    pub fn secret(&self) -> Date {
+        let days = get_secret_number(self.min.to_days(), self.max.to_days());
+        Date::from_days(days).expect("the day lies between two valid dates")
+    }
+
+    /// Parses user input into a date in the range of the game (see `Date::parse`).
+    ///
+    /// # Returns
+    /// Returns a `Result<Date, GuessError<Date>>`:
+    /// - `Ok(Date)` if the input is a date in the range of the game.
+    /// - `Err(GuessError::Empty)` if the input is blank.
+    /// - `Err(GuessError::Invalid)` if the input is not a date, or names a day that does
+    ///   not exist.
+    /// - `Err(GuessError::OutOfRange)` if the date is outside the range of the game.
+    pub fn parse(&self, input: &str) -> Result<Date, GuessError<Date>> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        let date = Date::parse(input, self.month_first).ok_or_else(|| GuessError::Invalid {
+            input: input.to_string(),
+            kind: InputKind::Date,
+        })?;
+        if !(self.min..=self.max).contains(&date) {
+            return Err(GuessError::OutOfRange {
+                value: date,
+                min: self.min,
+                max: self.max,
+            });
+        }
+        Ok(date)
+    }
+
+    /// Parses a line typed by the player into a command or a date.
+    ///
+    /// Lines starting with a colon are commands (see `Command::parse`); anything else is parsed
+    /// by `DateGame::parse`.
+    pub fn parse_player_input(&self, line: &str) -> Result<DateInput, GuessError<Date>> {
+        if let Some(command) = Command::parse(line) {
+            return command.map(DateInput::Command);
+        }
+
+        self.parse(line).map(DateInput::Guess)
+    }
+}
+
+/// Plays one game with a secret date and returns how it ended.
+///
+/// This works like `classic::play`, except that every guess is a date, and the feedback
+/// says whether the secret date is earlier or later. With `--date-hints`, a wrong guess
+/// also says whether its year, or its year and month, are right. Only the `:q` command is
+/// available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `game` - The range of dates and the settings of the game.
+pub fn play<R: BufRead, W: Write>(prompter: &mut Prompter<R, W>, game: &DateGame) -> GameOutcome {
+    let ui = prompter.ui();
+    let (min, max) = game.range();
+    prompter.say(&ui.format(Role::Info, "date-intro", &[("min", &min), ("max", &max)]));
+
+    let secret = game.secret();
+    let mut guess_count = GuessCount::new();
+
+    let outcome = loop {
+        let input = match prompter.next(|line| game.parse_player_input(line)) {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let guess = match input {
+            DateInput::Guess(guess) => guess,
+            DateInput::Command(Command::Quit) => break GameOutcome::Quit,
+            DateInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+
+        guess_count.increment();
+        match handle_guess(guess, &secret) {
+            GuessResult::TooSmall => {
+                prompter.say(&ui.format(Role::TooSmall, "date-later", &[("date", &guess)]))
+            }
+            GuessResult::TooBig => {
+                prompter.say(&ui.format(Role::TooBig, "date-earlier", &[("date", &guess)]))
+            }
+            GuessResult::Correct => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                break GameOutcome::Won;
+            }
+        }
+
+        if game.hints() {
+            match guess.matching(&secret) {
+                DateMatch::Year => prompter.say(&ui.text(Role::Info, "date-hint-year")),
+                DateMatch::Month => prompter.say(&ui.text(Role::Info, "date-hint-month")),
+                DateMatch::Nothing | DateMatch::Day => {}
+            }
+        }
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(Role::Headline, "reveal-date", count, &[("secret", &secret)]));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{handle_guess, GuessResult};
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    // Test which days exist
+    #[test]
+    fn valid_dates() {
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert!(Date::new(2023, 2, 29).is_none());
+        assert!(Date::new(1900, 2, 29).is_none());
+        assert!(Date::new(2000, 2, 29).is_some());
+        assert!(Date::new(2024, 4, 31).is_none());
+        assert!(Date::new(2024, 13, 1).is_none());
+        assert!(Date::new(0, 1, 1).is_none());
+        assert_eq!(date(987, 6, 5).to_string(), "0987-06-05");
+    }
+
+    // Test converting dates to and from day numbers
+    #[test]
+    fn day_numbers() {
+        assert_eq!(date(1970, 1, 1).to_days(), 0);
+        assert_eq!(date(2000, 3, 1).to_days(), 11_017);
+        assert_eq!(date(1969, 12, 31).to_days(), -1);
+        for days in [-719_162, -1, 0, 59, 11_016, 2_932_896] {
+            assert_eq!(Date::from_days(days).unwrap().to_days(), days);
+        }
+        assert_eq!(Date::from_days(-719_162), Some(date(1, 1, 1)));
+        assert_eq!(Date::from_days(2_932_896), Some(date(9999, 12, 31)));
+        assert_eq!(Date::from_days(2_932_897), None);
+    }
+
+    // Test the accepted date formats
+    #[test]
+    fn parse_formats() {
+        let expected = Some(date(2024, 3, 15));
+        for input in [
+            "2024-03-15",
+            "2024/3/15",
+            "15/03/2024",
+            "15.03.2024",
+            "March 15, 2024",
+            "mar 15 2024",
+            "15th of March 2024",
+            "15 de marzo de 2024",
+            "15 MARS 2024",
+        ] {
+            assert_eq!(Date::parse(input, false), expected, "{}", input);
+        }
+        assert_eq!(Date::parse("03/04/2024", false), Some(date(2024, 4, 3)));
+        assert_eq!(Date::parse("03/04/2024", true), Some(date(2024, 3, 4)));
+        assert_eq!(Date::parse("03/15/2024", false), expected);
+        assert_eq!(Date::parse("1er août 2024", false), Some(date(2024, 8, 1)));
+        for input in [
+            "2023-02-29",
+            "15/15/2024",
+            "March 2024",
+            "15 March",
+            "soon",
+            "1-2-3",
+        ] {
+            assert_eq!(Date::parse(input, false), None, "{}", input);
+        }
+    }
+
+    // Test parsing guesses in a game
+    #[test]
+    fn game_parse() {
+        let game = DateGame::new(2000, 2009, false, false);
+        assert_eq!(game.parse(" 2004-02-29\n"), Ok(date(2004, 2, 29)));
+        assert_eq!(game.parse(""), Err(GuessError::Empty));
+        assert_eq!(
+            game.parse("tomorrow"),
+            Err(GuessError::Invalid {
+                input: "tomorrow".to_string(),
+                kind: InputKind::Date
+            })
+        );
+        assert_eq!(
+            game.parse("2010-01-01"),
+            Err(GuessError::OutOfRange {
+                value: date(2010, 1, 1),
+                min: date(2000, 1, 1),
+                max: date(2009, 12, 31)
+            })
+        );
+        assert_eq!(
+            game.parse_player_input(":q"),
+            Ok(DateInput::Command(Command::Quit))
+        );
+        assert_eq!(Date::parse_input("1999-12-31"), Ok(date(1999, 12, 31)));
+    }
+
+    // Test the feedback and the hints
+    #[test]
+    fn feedback_and_hints() {
+        let secret = date(2024, 3, 15);
+        assert_eq!(
+            handle_guess(date(2023, 3, 15), &secret),
+            GuessResult::TooSmall
+        );
+        assert_eq!(
+            handle_guess(date(2024, 3, 16), &secret),
+            GuessResult::TooBig
+        );
+        assert_eq!(handle_guess(secret, &secret), GuessResult::Correct);
+        assert_eq!(date(2023, 3, 15).matching(&secret), DateMatch::Nothing);
+        assert_eq!(date(2024, 7, 15).matching(&secret), DateMatch::Year);
+        assert_eq!(date(2024, 3, 1).matching(&secret), DateMatch::Month);
+        assert_eq!(secret.matching(&secret), DateMatch::Day);
+    }
+
+    // Test that the secret date lies in the range
+    #[test]
+    fn secret_in_range() {
+        let game = DateGame::new(2024, 2024, true, false);
+        for _ in 0..100 {
+            assert_eq!(game.secret().year(), 2024);
+        }
+    }
+}
@@ -0,0 +1,760 @@
+use crate::{
+    command::Command,
+    get_secret_number, handle_guess,
+    prompt::Prompter,
+    theme::Role,
+    words::{self, WordsError},
+    GameOutcome, GuessCount, GuessError, GuessResult, Guessable, Incrementable, Parsable,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    io::{BufRead, Write},
+    num::{IntErrorKind, ParseIntError},
+};
+
+/// The largest number of decimal places a `Decimal` can have.
+pub const MAX_PLACES: u32 = 9;
+
+/// The number of decimal places used when only a tolerance is given, which makes the
+/// secret number behave like a real number.
+pub const REAL_PLACES: u32 = 6;
+
+/// A fixed-point decimal number, such as `3.14` or `-0.005`.
+///
+/// The `Decimal` struct stores a number as a whole count of `units`, each worth
+/// `10^-places`, so `3.14` with 2 places is 314 units. Working in units keeps every
+/// calculation exact, which floating point numbers cannot do for values like `0.1`.
+/// Decimals with a different number of places compare by their value, so `1.5` equals
+/// `1.50`.
+///
+/// `Display` always writes a `.` as the decimal separator; use `Decimal::localized` to
+/// show a decimal with the separator of the player's language.
+///
+/// # Fields
+/// - `units`: The value of the number in units of `10^-places`.
+/// - `places`: The number of digits after the decimal separator, at most `MAX_PLACES`.
+#[derive(Debug, Clone, Copy)]
+pub struct Decimal {
+    units: i64,
+    places: u32,
+}
+
+/// Enum to represent why text could not be read as a `Decimal`.
+///
+/// # Variants
+///
+/// - `Int(ParseIntError)`: The text is not a number, or it is too large. The error is the
+///   one `str::parse` gives for the digits, so it can be handled like any integer error.
+/// - `TooPrecise`: The text has more decimal places than allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseDecimalError {
+    Int(ParseIntError),
+    TooPrecise,
+}
+
+impl Decimal {
+    /// Creates a decimal worth `units * 10^-places`.
+    ///
+    /// # Panics
+    /// Panics if `places` is greater than `MAX_PLACES`.
+    pub fn new(units: i64, places: u32) -> Decimal {
+        assert!(
+            places <= MAX_PLACES,
+            "a decimal has at most {} places",
+            MAX_PLACES
+        );
+        Decimal { units, places }
+    }
+
+    /// Creates a decimal with `places` places from a whole number, or returns `None` if it is
+    /// too large to be stored with that many places.
+    pub fn from_integer(value: i64, places: u32) -> Option<Decimal> {
+        value
+            .checked_mul(10i64.pow(places))
+            .map(|units| Decimal::new(units, places))
+    }
+
+    /// Returns the value of the number in units of `10^-places`.
+    pub fn units(&self) -> i64 {
+        self.units
+    }
+
+    /// Returns the number of digits after the decimal separator.
+    pub fn places(&self) -> u32 {
+        self.places
+    }
+
+    /// Returns the fewest decimal places that can hold this value exactly, e.g. 1 for `0.50`.
+    pub fn scale(&self) -> u32 {
+        (0..self.places)
+            .find(|&places| self.rescale(places).is_some())
+            .unwrap_or(self.places)
+    }
+
+    /// Returns the same value with `places` decimal places, or `None` if it would lose
+    /// digits or no longer fit.
+    pub fn rescale(&self, places: u32) -> Option<Decimal> {
+        if places >= self.places {
+            let units = self.units.checked_mul(10i64.pow(places - self.places))?;
+            return Some(Decimal::new(units, places));
+        }
+        let factor = 10i64.pow(self.places - places);
+        (self.units % factor == 0).then(|| Decimal::new(self.units / factor, places))
+    }
+
+    /// Returns the distance between two decimals, with the larger number of places of both.
+    pub fn abs_diff(&self, other: &Decimal) -> Decimal {
+        let places = self.places.max(other.places);
+        let diff = self.scaled(places).abs_diff(other.scaled(places));
+        Decimal::new(i64::try_from(diff).unwrap_or(i64::MAX), places)
+    }
+
+    /// Returns this decimal for display with `separator` between the whole and the
+    /// fractional part, such as `,` in Spanish and French.
+    pub fn localized(self, separator: char) -> LocalizedDecimal {
+        LocalizedDecimal {
+            value: self,
+            separator,
+        }
+    }
+
+    /// Parses a decimal number with exactly `places` decimal places.
+    ///
+    /// The number may have a sign, and either `.` or `,` can separate the whole part from the
+    /// fractional part. Fewer decimal places than `places` are filled up with zeros, so `2` and
+    /// `2.5` are `2.00` and `2.50` with 2 places. Use `Decimal::parse_localized` to accept
+    /// only the separator of one language.
+    ///
+    /// # Arguments
+    /// * `input` - The text to parse, without surrounding whitespace.
+    /// * `places` - The number of decimal places of the result.
+    ///
+    /// # Returns
+    /// Returns a `Result`:
+    /// - `Ok(Decimal)` with `places` decimal places.
+    /// - `Err(ParseDecimalError::TooPrecise)` if the input has more decimal places.
+    /// - `Err(ParseDecimalError::Int)` if the input is empty, is not a number or is too large.
+    pub fn parse(input: &str, places: u32) -> Result<Decimal, ParseDecimalError> {
+        Decimal::parse_with(input, places, &['.', ','])
+    }
+
+    /// Parses a decimal number like `Decimal::parse`, but with only `separator` between the
+    /// whole part and the fractional part.
+    ///
+    /// Any other separator makes the input invalid, so in English, where the separator is
+    /// `.`, a number written with digit grouping such as `1,000` is rejected instead of being
+    /// read as `1.000`.
+    pub fn parse_localized(
+        input: &str,
+        places: u32,
+        separator: char,
+    ) -> Result<Decimal, ParseDecimalError> {
+        Decimal::parse_with(input, places, &[separator])
+    }
+
+    /// Parses a decimal number with any of `separators` between the whole part and the
+    /// fractional part.
+    fn parse_with(
+        input: &str,
+        places: u32,
+        separators: &[char],
+    ) -> Result<Decimal, ParseDecimalError> {
+        let (negative, unsigned) = match input.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, input.strip_prefix('+').unwrap_or(input)),
+        };
+        let (whole, fraction) = unsigned.split_once(separators).unwrap_or((unsigned, ""));
+
+        let digits = format!("{}{:0<width$}", whole, fraction, width = places as usize);
+        if (whole.is_empty() && fraction.is_empty()) || !digits.bytes().all(|b| b.is_ascii_digit())
+        {
+            // Let the integer parser explain what is wrong, e.g. empty or invalid input.
+            return Err(ParseDecimalError::Int(
+                input.parse::<i64>().err().unwrap_or_else(invalid_digit),
+            ));
+        }
+        if fraction.len() > places as usize {
+            return Err(ParseDecimalError::TooPrecise);
+        }
+
+        let sign = if negative { "-" } else { "" };
+        let units = format!("{}{}", sign, digits)
+            .parse()
+            .map_err(ParseDecimalError::Int)?;
+        Ok(Decimal::new(units, places))
+    }
+
+    /// Returns the value in units of `10^-places`, for `places` at least `self.places`.
+    fn scaled(&self, places: u32) -> i128 {
+        i128::from(self.units) * 10i128.pow(places - self.places)
+    }
+
+    /// Writes the decimal with `separator` between the whole and the fractional part.
+    fn write(&self, f: &mut fmt::Formatter<'_>, separator: char) -> fmt::Result {
+        let sign = if self.units < 0 { "-" } else { "" };
+        let factor = 10u64.pow(self.places);
+        let (whole, fraction) = (
+            self.units.unsigned_abs() / factor,
+            self.units.unsigned_abs() % factor,
+        );
+        if self.places == 0 {
+            return write!(f, "{}{}", sign, whole);
+        }
+        write!(
+            f,
+            "{}{}{}{:0width$}",
+            sign,
+            whole,
+            separator,
+            fraction,
+            width = self.places as usize
+        )
+    }
+}
+
+/// Returns the error `str::parse` gives for a bad digit.
+fn invalid_digit() -> ParseIntError {
+    "x".parse::<i64>().unwrap_err()
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.scaled(MAX_PLACES).cmp(&other.scaled(MAX_PLACES))
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.scaled(MAX_PLACES).hash(state);
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, '.')
+    }
+}
+
+/// A `Decimal` shown with the decimal separator of a language, made by `Decimal::localized`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct LocalizedDecimal {
+    value: Decimal,
+    separator: char,
+}
+
+impl fmt::Display for LocalizedDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.write(f, self.separator)
+    }
+}
+
+/// A struct representing a decimal guess, or a decimal secret number.
+///
+/// A `DecimalGuess` holds its value and a tolerance. When two guesses are compared, values
+/// closer than the larger of their tolerances count as equal, so a secret number with a
+/// tolerance of `0.05` is found by any guess within `0.05` of it. Guesses typed by the player
+/// have no tolerance of their own.
+///
+/// # Fields
+/// - `value`: The value of the guess.
+/// - `tolerance`: How far from the value a guess may be and still be correct.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecimalGuess {
+    value: Decimal,
+    tolerance: Decimal,
+}
+
+impl DecimalGuess {
+    /// Creates a guess that only equals exactly the same value.
+    pub fn exact(value: Decimal) -> DecimalGuess {
+        DecimalGuess::with_tolerance(value, Decimal::new(0, value.places()))
+    }
+
+    /// Creates a guess that equals every value within `tolerance` of `value`.
+    pub fn with_tolerance(value: Decimal, tolerance: Decimal) -> DecimalGuess {
+        DecimalGuess { value, tolerance }
+    }
+
+    /// Returns the value of the guess.
+    pub fn value(&self) -> Decimal {
+        self.value
+    }
+
+    /// Returns how far from the value a guess may be and still be correct.
+    pub fn tolerance(&self) -> Decimal {
+        self.tolerance
+    }
+}
+
+/// Trait implementation for `DecimalGuess` to make it "guessable".
+///
+/// A decimal guess is `GuessResult::Correct` when its value is at most the larger of the two
+/// tolerances away from the other value; otherwise the values are compared.
+impl Guessable for DecimalGuess {
+    type Feedback = GuessResult;
+
+    fn compare(&self, other: &Self) -> GuessResult {
+        let tolerance = self.tolerance.max(other.tolerance);
+        if self.value.abs_diff(&other.value) <= tolerance {
+            GuessResult::Correct
+        } else {
+            self.value.cmp(&other.value).into()
+        }
+    }
+}
+
+/// Trait implementation for `DecimalGuess` to make it parsable from a string input.
+///
+/// The input is parsed by `DecimalGame::parse` for the default game, with 2 decimal places
+/// over the classic range of 1.00 to 100.00.
+impl Parsable for DecimalGuess {
+    type Value = Decimal;
+
+    fn parse_input(input: &str) -> Result<DecimalGuess, GuessError<Decimal>> {
+        DecimalGame::default().parse(input)
+    }
+}
+
+/// Enum to represent one line of input from the player in the decimal game.
+///
+/// # Variants
+///
+/// - `Guess(DecimalGuess)`: The player made a guess.
+/// - `Command(Command)`: The player typed a command such as `:q`.
+#[derive(Debug, PartialEq)]
+pub enum DecimalInput {
+    Guess(DecimalGuess),
+    Command(Command),
+}
+
+/// A struct holding the settings of a game played with decimal numbers.
+///
+/// # Fields
+/// - `min`, `max`: The smallest and largest possible secret number.
+/// - `tolerance`: How far from the secret number a guess may be and still be correct.
+/// - `separator`: The decimal separator guesses are typed with, `.` unless changed with
+///   `DecimalGame::with_separator`.
+///
+/// The range and the tolerance have the same number of decimal places, which is the precision
+/// of the game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecimalGame {
+    min: Decimal,
+    max: Decimal,
+    tolerance: Decimal,
+    separator: char,
+}
+
+impl Default for DecimalGame {
+    fn default() -> Self {
+        DecimalGame::new(
+            Decimal::new(100, 2),
+            Decimal::new(10_000, 2),
+            Decimal::new(0, 2),
+        )
+    }
+}
+
+impl DecimalGame {
+    /// Creates the settings for a game over `min..=max` with the given tolerance.
+    ///
+    /// # Panics
+    /// Panics if the three decimals do not have the same number of places.
+    pub fn new(min: Decimal, max: Decimal, tolerance: Decimal) -> DecimalGame {
+        assert!(
+            min.places() == max.places() && max.places() == tolerance.places(),
+            "the range and the tolerance must have the same number of decimal places"
+        );
+        DecimalGame {
+            min,
+            max,
+            tolerance,
+            separator: '.',
+        }
+    }
+
+    /// Returns the same game with guesses typed with `separator`, such as the decimal
+    /// separator of the player's language (see `Locale::decimal_separator`).
+    pub fn with_separator(self, separator: char) -> DecimalGame {
+        DecimalGame { separator, ..self }
+    }
+
+    /// Returns the number of decimal places of the game.
+    pub fn places(&self) -> u32 {
+        self.min.places()
+    }
+
+    /// Returns the smallest and largest possible secret number.
+    pub fn range(&self) -> (Decimal, Decimal) {
+        (self.min, self.max)
+    }
+
+    /// Returns how far from the secret number a guess may be and still be correct.
+    pub fn tolerance(&self) -> Decimal {
+        self.tolerance
+    }
+
+    /// Draws a random secret number from the range of the game, carrying its tolerance.
+    pub fn secret(&self) -> DecimalGuess {
+        let units = get_secret_number(self.min.units(), self.max.units());
+        DecimalGuess::with_tolerance(Decimal::new(units, self.places()), self.tolerance)
+    }
+
+    /// Parses user input into a guess in the range of the game.
+    ///
+    /// Guesses may be typed with the decimal separator of the game and with up to `places`
+    /// decimal places, or as a whole number written in words such as "seven". Any other
+    /// separator, such as a `,` grouping the digits of `1,000` in an English game, makes the
+    /// guess not a number.
+    ///
+    /// # Arguments
+    /// * `input` - The user input to parse.
+    ///
+    /// # Returns
+    /// Returns a `Result<DecimalGuess, GuessError<Decimal>>`:
+    /// - `Ok(DecimalGuess)` if the input is a number in the range of the game.
+    /// - `Err(GuessError::Empty)` if the input is blank.
+    /// - `Err(GuessError::TooPrecise)` if the input has more decimal places than the game.
+    /// - `Err(GuessError::Overflow)` if the number is far too large.
+    /// - `Err(GuessError::AmbiguousNumber)` if the input is number words that don't form one
+    ///   number.
+    /// - `Err(GuessError::NotANumber)` if the input cannot be read as a number at all.
+    /// - `Err(GuessError::OutOfRange)` if the number is outside the range of the game.
+    pub fn parse(&self, input: &str) -> Result<DecimalGuess, GuessError<Decimal>> {
+        let input = input.trim();
+        let input_string = input.to_string();
+        let source = match Decimal::parse_localized(input, self.places(), self.separator) {
+            Ok(value) => return self.in_range(value),
+            Err(ParseDecimalError::TooPrecise) => {
+                return Err(GuessError::TooPrecise {
+                    input: input_string,
+                    places: self.places(),
+                })
+            }
+            Err(ParseDecimalError::Int(source)) => source,
+        };
+        match source.kind() {
+            IntErrorKind::Empty => Err(GuessError::Empty),
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Err(GuessError::Overflow {
+                input: input_string,
+            }),
+            _ => match words::parse_words_any(input) {
+                Ok(value) => match Decimal::from_integer(i64::from(value), self.places()) {
+                    Some(value) => self.in_range(value),
+                    None => Err(GuessError::Overflow {
+                        input: input_string,
+                    }),
+                },
+                Err(WordsError::Ambiguous) => Err(GuessError::AmbiguousNumber {
+                    input: input_string,
+                }),
+                Err(WordsError::NotWords) => Err(GuessError::NotANumber {
+                    input: input_string,
+                    source,
+                }),
+            },
+        }
+    }
+
+    /// Parses a line typed by the player into a command or a guess.
+    ///
+    /// Lines starting with a colon are commands (see `Command::parse`); anything else is parsed
+    /// by `DecimalGame::parse`.
+    pub fn parse_player_input(&self, line: &str) -> Result<DecimalInput, GuessError<Decimal>> {
+        if let Some(command) = Command::parse(line) {
+            return command.map(DecimalInput::Command);
+        }
+
+        self.parse(line).map(DecimalInput::Guess)
+    }
+
+    /// Returns how precise a correct guess was, as a score from 0 to 100.
+    ///
+    /// A guess of exactly the secret number scores 100, and one at the edge of the tolerance
+    /// scores 0. Without a tolerance, every correct guess is exact.
+    ///
+    /// # Arguments
+    /// * `guess` - The correct guess.
+    /// * `secret` - The secret number.
+    pub fn precision_score(&self, guess: Decimal, secret: Decimal) -> u32 {
+        let tolerance = i128::from(self.tolerance.units());
+        if tolerance == 0 {
+            return 100;
+        }
+        let error = i128::from(
+            guess
+                .abs_diff(&secret)
+                .rescale(self.places())
+                .map_or(self.tolerance.units(), |error| error.units()),
+        );
+        let score = (100 * (tolerance - error.min(tolerance))) / tolerance;
+        score as u32
+    }
+
+    /// Turns a value into a guess if it lies in the range of the game.
+    fn in_range(&self, value: Decimal) -> Result<DecimalGuess, GuessError<Decimal>> {
+        if !(self.min..=self.max).contains(&value) {
+            return Err(GuessError::OutOfRange {
+                value,
+                min: self.min,
+                max: self.max,
+            });
+        }
+        Ok(DecimalGuess::exact(value))
+    }
+}
+
+/// Plays one game with a decimal secret number and returns how it ended.
+///
+/// This works like `classic::play`, except that guesses are decimals, typed with the
+/// decimal separator of the player's language, and that guesses within the tolerance of the
+/// secret number are correct. Such a win also tells the player how precise the guess was.
+/// Only the `:q` command is available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `game` - The range, precision and tolerance of the game.
+pub fn play<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    game: &DecimalGame,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    let separator = ui.catalog.locale().decimal_separator();
+    let game = game.with_separator(separator);
+    let (min, max) = game.range();
+    let (min, max) = (min.localized(separator), max.localized(separator));
+    prompter.say(&ui.format(Role::Info, "range", &[("min", &min), ("max", &max)]));
+    let tolerance = game.tolerance();
+    if tolerance.units() > 0 {
+        let tolerance = tolerance.localized(separator);
+        prompter.say(&ui.format(Role::Info, "tolerance", &[("tolerance", &tolerance)]));
+    }
+
+    let secret_number = game.secret();
+    let secret = secret_number.value().localized(separator);
+    let mut guess_count = GuessCount::new();
+
+    let outcome = loop {
+        // Show the values in errors with the separator of the player's language.
+        let input = match prompter.next(|line| {
+            game.parse_player_input(line)
+                .map_err(|e| e.map_value(|value| value.localized(separator)))
+        }) {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let guess = match input {
+            DecimalInput::Guess(guess) => guess,
+            DecimalInput::Command(Command::Quit) => break GameOutcome::Quit,
+            DecimalInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+        let value = guess.value();
+
+        guess_count.increment();
+        match handle_guess(guess, &secret_number) {
+            GuessResult::TooSmall => prompter.say(&ui.text(Role::TooSmall, "too-small")),
+            GuessResult::TooBig => prompter.say(&ui.text(Role::TooBig, "too-big")),
+            GuessResult::Correct => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                // Say how close the guess was when it did not have to be exact.
+                if tolerance.units() > 0 {
+                    let error = value.abs_diff(&secret_number.value());
+                    let error = error.localized(separator);
+                    let score = game.precision_score(value, secret_number.value());
+                    prompter.say(&ui.format(
+                        Role::Info,
+                        "win-precision",
+                        &[("secret", &secret), ("error", &error), ("score", &score)],
+                    ));
+                }
+                break GameOutcome::Won;
+            }
+        }
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(Role::Headline, "reveal", count, &[("secret", &secret)]));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{handle_guess, GuessResult};
+
+    fn game(min: i64, max: i64, tolerance: i64, places: u32) -> DecimalGame {
+        DecimalGame::new(
+            Decimal::new(min, places),
+            Decimal::new(max, places),
+            Decimal::new(tolerance, places),
+        )
+    }
+
+    // Test parsing decimals with either separator
+    #[test]
+    fn parse_decimals() {
+        assert_eq!(Decimal::parse("3.14", 2), Ok(Decimal::new(314, 2)));
+        assert_eq!(Decimal::parse("3,14", 2), Ok(Decimal::new(314, 2)));
+        assert_eq!(Decimal::parse("3", 2), Ok(Decimal::new(300, 2)));
+        assert_eq!(Decimal::parse(".5", 2), Ok(Decimal::new(50, 2)));
+        assert_eq!(Decimal::parse("-0.05", 2), Ok(Decimal::new(-5, 2)));
+        assert_eq!(
+            Decimal::parse_localized("3,14", 2, ','),
+            Ok(Decimal::new(314, 2))
+        );
+        assert!(Decimal::parse_localized("3.14", 2, ',').is_err());
+        assert!(Decimal::parse_localized("1,000", 3, '.').is_err());
+        assert_eq!(
+            Decimal::parse("3.141", 2),
+            Err(ParseDecimalError::TooPrecise)
+        );
+        for bad in ["", "-", ".", "abc", "1.2.3", "--5", "1.-5"] {
+            assert!(
+                matches!(Decimal::parse(bad, 2), Err(ParseDecimalError::Int(_))),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    // Test displaying decimals with and without a localized separator
+    #[test]
+    fn display_decimals() {
+        assert_eq!(Decimal::new(314, 2).to_string(), "3.14");
+        assert_eq!(Decimal::new(-5, 2).to_string(), "-0.05");
+        assert_eq!(Decimal::new(7, 0).to_string(), "7");
+        assert_eq!(Decimal::new(1050, 2).localized(',').to_string(), "10,50");
+    }
+
+    // Test that decimals compare by value whatever their number of places
+    #[test]
+    fn compare_and_rescale() {
+        assert_eq!(Decimal::new(15, 1), Decimal::new(150, 2));
+        assert!(Decimal::new(15, 1) < Decimal::new(151, 2));
+        assert_eq!(Decimal::new(150, 2).rescale(1), Some(Decimal::new(15, 1)));
+        assert_eq!(Decimal::new(151, 2).rescale(1), None);
+        assert_eq!(Decimal::new(50, 2).scale(), 1);
+        assert_eq!(
+            Decimal::new(5, 1).abs_diff(&Decimal::new(-25, 2)),
+            Decimal::new(75, 2)
+        );
+    }
+
+    // Test that guesses within the tolerance are correct
+    #[test]
+    fn tolerance_counts_as_correct() {
+        let secret = DecimalGuess::with_tolerance(Decimal::new(314, 2), Decimal::new(5, 2));
+        let guess = |units| DecimalGuess::exact(Decimal::new(units, 2));
+        assert_eq!(handle_guess(guess(309), &secret), GuessResult::Correct);
+        assert_eq!(handle_guess(guess(319), &secret), GuessResult::Correct);
+        assert_eq!(handle_guess(guess(308), &secret), GuessResult::TooSmall);
+        assert_eq!(handle_guess(guess(320), &secret), GuessResult::TooBig);
+
+        let exact = DecimalGuess::exact(Decimal::new(314, 2));
+        assert_eq!(handle_guess(guess(313), &exact), GuessResult::TooSmall);
+        assert_eq!(handle_guess(guess(314), &exact), GuessResult::Correct);
+    }
+
+    // Test parsing guesses in a game
+    #[test]
+    fn game_parse() {
+        let game = game(0, 1000, 0, 2);
+        assert_eq!(
+            game.parse(" 2.5 \n").map(|guess| guess.value()),
+            Ok(Decimal::new(250, 2))
+        );
+        assert!(matches!(
+            game.parse("1,000"),
+            Err(GuessError::NotANumber { .. })
+        ));
+        assert_eq!(
+            game.with_separator(',')
+                .parse(" 2,5 \n")
+                .map(|guess| guess.value()),
+            Ok(Decimal::new(250, 2))
+        );
+        assert!(matches!(
+            game.with_separator(',').parse("2.5"),
+            Err(GuessError::NotANumber { .. })
+        ));
+        assert_eq!(
+            game.parse("seven").map(|guess| guess.value()),
+            Ok(Decimal::new(700, 2))
+        );
+        assert_eq!(game.parse(""), Err(GuessError::Empty));
+        assert_eq!(
+            game.parse("1.234"),
+            Err(GuessError::TooPrecise {
+                input: "1.234".to_string(),
+                places: 2
+            })
+        );
+        assert_eq!(
+            game.parse("10.01"),
+            Err(GuessError::OutOfRange {
+                value: Decimal::new(1001, 2),
+                min: Decimal::new(0, 2),
+                max: Decimal::new(1000, 2)
+            })
+        );
+        assert!(matches!(
+            game.parse("abc"),
+            Err(GuessError::NotANumber { .. })
+        ));
+        assert!(matches!(
+            game.parse("99999999999999999999"),
+            Err(GuessError::Overflow { .. })
+        ));
+        assert_eq!(
+            game.parse_player_input(":q"),
+            Ok(DecimalInput::Command(Command::Quit))
+        );
+        assert_eq!(
+            DecimalGuess::parse_input("42.42").map(|guess| guess.value()),
+            Ok(Decimal::new(4242, 2))
+        );
+    }
+
+    // Test that the secret number lies in the range and the precision score
+    #[test]
+    fn secret_and_precision_score() {
+        let game = game(-100, 100, 10, 2);
+        for _ in 0..100 {
+            let secret = game.secret();
+            assert!((Decimal::new(-100, 2)..=Decimal::new(100, 2)).contains(&secret.value()));
+            assert_eq!(secret.tolerance(), Decimal::new(10, 2));
+        }
+        let secret = Decimal::new(50, 2);
+        assert_eq!(game.precision_score(secret, secret), 100);
+        assert_eq!(game.precision_score(Decimal::new(53, 2), secret), 70);
+        assert_eq!(game.precision_score(Decimal::new(40, 2), secret), 0);
+        assert_eq!(
+            DecimalGame::default().precision_score(Decimal::new(1, 2), Decimal::new(1, 2)),
+            100
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    command::Command, get_secret_number, handle_guess, integer::Integer, parse_player_input,
+    prompt::Prompter, theme::Role, GameOutcome, Guess, GuessCount, GuessResult, Incrementable,
+    PlayerInput,
+};
+use std::{
+    collections::VecDeque,
+    io::{BufRead, Write},
+};
+
+/// The largest delay that can be chosen, in guesses.
+pub const MAX_DELAY: u32 = 100;
+
+/// Enum to represent when the feedback for a guess is shown in a game with delayed feedback.
+///
+/// # Variants
+///
+/// - `After(d)`: The feedback for guess `n` is shown right after guess `n + d`.
+/// - `Every(n)`: The feedback is held back until `n` guesses are waiting, and then shown for
+///   all of them at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Delay {
+    After(u32),
+    Every(u32),
+}
+
+/// The feedback for one guess, as shown once its delay is over.
+///
+/// # Fields
+/// - `number`: Which guess the feedback is for, counting from 1.
+/// - `value`: The value that was guessed.
+/// - `result`: Whether the guess was too small or too big.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feedback<T = u32> {
+    pub number: u32,
+    pub value: T,
+    pub result: GuessResult,
+}
+
+/// A queue holding back the feedback for guesses until the `Delay` allows it to be shown.
+///
+/// Every guess is numbered as it is pushed, so the feedback can say which guess it refers to
+/// when it finally comes out.
+///
+/// # Fields
+/// - `delay`: When the feedback is shown.
+/// - `pending`: The feedback not shown yet, oldest first.
+/// - `guesses`: The number of guesses pushed so far.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedbackQueue<T = u32> {
+    delay: Delay,
+    pending: VecDeque<Feedback<T>>,
+    guesses: u32,
+}
+
+impl<T> FeedbackQueue<T> {
+    /// Creates an empty queue that holds back feedback as set by `delay`.
+    pub fn new(delay: Delay) -> FeedbackQueue<T> {
+        FeedbackQueue {
+            delay,
+            pending: VecDeque::new(),
+            guesses: 0,
+        }
+    }
+
+    /// Returns how many guesses are still waiting for their feedback.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Queues the feedback for a new guess and returns the feedback that is due now.
+    ///
+    /// # Arguments
+    /// * `value` - The value that was guessed.
+    /// * `result` - The result of comparing it with the secret.
+    ///
+    /// # Returns
+    /// Returns the feedback whose delay is over, oldest first, which may be none at all.
+    pub fn push(&mut self, value: T, result: GuessResult) -> Vec<Feedback<T>> {
+        self.guesses += 1;
+        self.pending.push_back(Feedback {
+            number: self.guesses,
+            value,
+            result,
+        });
+        let due = match self.delay {
+            Delay::After(delay) => self.pending.len().saturating_sub(delay as usize),
+            Delay::Every(size) if self.pending.len() >= size as usize => self.pending.len(),
+            Delay::Every(_) => 0,
+        };
+        self.pending.drain(..due).collect()
+    }
+}
+
+/// Plays one game with delayed feedback and returns how it ended.
+///
+/// This works like `classic::play`, except that the results of the guesses go through a
+/// `FeedbackQueue`, which holds them back as set by `delay`: each is shown a number of guesses
+/// late, or all of them together once enough are waiting. Each result says which guess it is
+/// for. A correct guess still ends the game at once, so only the direction of the wrong
+/// guesses is delayed. Guesses can use `last` for the previous guess. Only the `:q` command is
+/// available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `delay` - When the feedback for a guess is shown.
+/// * `min`, `max` - The smallest and largest possible secret number.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    delay: Delay,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    let intro = match delay {
+        Delay::After(guesses) => ui.plural(Role::Info, "delay-after", u64::from(guesses), &[]),
+        Delay::Every(guesses) => ui.plural(Role::Info, "delay-every", u64::from(guesses), &[]),
+    };
+    prompter.say(&intro);
+
+    let secret = get_secret_number(min.clone(), max.clone());
+    let secret_number = Guess::in_range(secret, min.clone(), max.clone())
+        .expect("Failed to generate secret number");
+    let mut queue = FeedbackQueue::new(delay);
+    let mut guess_count = GuessCount::new();
+    let mut last: Option<T> = None;
+
+    let outcome = loop {
+        let input = match prompter
+            .next(|line| parse_player_input(line, min.clone(), max.clone(), last.clone()))
+        {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let guess = match input {
+            PlayerInput::Guess(guess) => guess,
+            PlayerInput::Command(Command::Quit) => break GameOutcome::Quit,
+            PlayerInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+
+        guess_count.increment();
+        let value = guess.value();
+        let result = handle_guess(guess, &secret_number);
+        if result == GuessResult::Correct {
+            let count = u64::from(guess_count.value());
+            prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+            break GameOutcome::Won;
+        }
+
+        let due = queue.push(value.clone(), result);
+        if due.is_empty() {
+            let pending = queue.pending() as u64;
+            prompter.say(&ui.plural(Role::Info, "feedback-pending", pending, &[]));
+        }
+        for feedback in due {
+            let result = ui.result_text(feedback.result);
+            prompter.say(&ui.catalog.format(
+                "delayed-feedback",
+                &[
+                    ("number", &feedback.number),
+                    ("value", &feedback.value),
+                    ("result", &result),
+                ],
+            ));
+        }
+        last = Some(value);
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(
+            Role::Headline,
+            "reveal",
+            count,
+            &[("secret", &secret_number.value())],
+        ));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use GuessResult::*;
+
+    fn numbers(feedback: Vec<Feedback<i64>>) -> Vec<u32> {
+        feedback.iter().map(|feedback| feedback.number).collect()
+    }
+
+    // Test that feedback comes a fixed number of guesses late
+    #[test]
+    fn delay_after() {
+        let mut queue = FeedbackQueue::new(Delay::After(2));
+        assert_eq!(numbers(queue.push(50, TooBig)), []);
+        assert_eq!(numbers(queue.push(25, TooSmall)), []);
+        assert_eq!(queue.pending(), 2);
+        assert_eq!(
+            queue.push(37, TooSmall),
+            [Feedback {
+                number: 1,
+                value: 50,
+                result: TooBig
+            }]
+        );
+        assert_eq!(numbers(queue.push(43, TooBig)), [2]);
+        assert_eq!(queue.pending(), 2);
+    }
+
+    // Test that feedback comes in batches
+    #[test]
+    fn delay_every() {
+        let mut queue = FeedbackQueue::new(Delay::Every(3));
+        assert_eq!(numbers(queue.push(50, TooBig)), []);
+        assert_eq!(numbers(queue.push(25, TooSmall)), []);
+        assert_eq!(numbers(queue.push(37, TooSmall)), [1, 2, 3]);
+        assert_eq!(queue.pending(), 0);
+        assert_eq!(numbers(queue.push(43, TooBig)), []);
+        assert_eq!(queue.pending(), 1);
+    }
+}
@@ -0,0 +1,390 @@
+use crate::{
+    command::Command, get_secret_number, handle_guess, prompt::Prompter, theme::Role, GameOutcome,
+    GuessCount, GuessError, GuessResult, Guessable, Incrementable, InputKind, Parsable,
+};
+use std::{
+    fmt, fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+/// A word from a dictionary, which can be guessed or be the secret word.
+///
+/// Words are compared alphabetically by their `key`, which ignores case and accents, so
+/// "Élan" comes between "eagle" and "engine" and equals "elan".
+///
+/// # Fields
+/// - `text`: The word as it is shown, in lowercase.
+/// - `key`: The word with accents removed, used for sorting and lookups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word {
+    text: String,
+    key: String,
+}
+
+impl Word {
+    /// Creates a word from any text, normalizing it with `normalize` and `fold`.
+    pub fn new(text: &str) -> Word {
+        Word {
+            text: normalize(text),
+            key: fold(text),
+        }
+    }
+
+    /// Returns the word as it is shown.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the word with accents removed, as it is sorted.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Trait implementation for `Word` to make it "guessable".
+///
+/// Words are ordered alphabetically by their keys, so `handle_guess` tells whether the secret
+/// word comes before (`TooBig`) or after (`TooSmall`) the guess.
+impl Guessable for Word {
+    type Feedback = GuessResult;
+
+    fn compare(&self, other: &Self) -> GuessResult {
+        self.key.cmp(&other.key).into()
+    }
+}
+
+/// Trait implementation for `Word` to make it parsable from a string input.
+///
+/// The input is parsed by `Dictionary::parse` with the built-in word list.
+impl Parsable for Word {
+    type Value = String;
+
+    fn parse_input(input: &str) -> Result<Word, GuessError<String>> {
+        Dictionary::builtin().parse(input)
+    }
+}
+
+/// Normalizes a word for display: surrounding whitespace is removed, runs of whitespace
+/// become one space, and every letter is lowercase.
+pub fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Folds a word for comparison: it is normalized and accented Latin letters are replaced by
+/// their plain letters, e.g. "Été" becomes "ete" and "Straße" becomes "strasse".
+pub fn fold(text: &str) -> String {
+    let mut folded = String::new();
+    for c in normalize(text).chars() {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => folded.push('a'),
+            'ç' => folded.push('c'),
+            'è' | 'é' | 'ê' | 'ë' => folded.push('e'),
+            'ì' | 'í' | 'î' | 'ï' => folded.push('i'),
+            'ñ' => folded.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => folded.push('o'),
+            'ù' | 'ú' | 'û' | 'ü' => folded.push('u'),
+            'ý' | 'ÿ' => folded.push('y'),
+            'æ' => folded.push_str("ae"),
+            'œ' => folded.push_str("oe"),
+            'ß' => folded.push_str("ss"),
+            c => folded.push(c),
+        }
+    }
+    folded
+}
+
+/// Enum to represent one line of input from the player in word mode.
+///
+/// # Variants
+///
+/// - `Guess(Word)`: The player guessed a word.
+/// - `Command(Command)`: The player typed a command such as `:q`.
+#[derive(Debug, PartialEq)]
+pub enum WordInput {
+    Guess(Word),
+    Command(Command),
+}
+
+/// A sorted list of the words that can be the secret word, and that can be guessed.
+///
+/// # Fields
+/// - `words`: The words, sorted by their keys, with no two words sharing a key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dictionary {
+    words: Vec<Word>,
+}
+
+impl Dictionary {
+    /// Reads a dictionary from text with one word per line.
+    ///
+    /// Blank lines and lines starting with `#` are ignored. Words are normalized, and when
+    /// several lines give the same word, e.g. "Apple" and "apple", the first one is kept.
+    pub fn from_text(text: &str) -> Dictionary {
+        let mut words: Vec<Word> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(Word::new)
+            .collect();
+        // A stable sort keeps the first of several equal words in front.
+        words.sort_by(|a, b| a.key.cmp(&b.key));
+        words.dedup_by(|a, b| a.key == b.key);
+        Dictionary { words }
+    }
+
+    /// Reads a dictionary from a file with one word per line (see `Dictionary::from_text`).
+    ///
+    /// # Errors
+    /// Returns any error raised while reading the file.
+    pub fn load(path: &Path) -> io::Result<Dictionary> {
+        Ok(Dictionary::from_text(&fs::read_to_string(path)?))
+    }
+
+    /// Returns the built-in list of common English words.
+    pub fn builtin() -> Dictionary {
+        Dictionary::from_text(include_str!("../dictionaries/en.txt"))
+    }
+
+    /// Returns the words, in alphabetical order.
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// Returns the number of words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if the dictionary has no words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Draws a random secret word.
+    ///
+    /// # Panics
+    /// Panics if the dictionary is empty.
+    pub fn secret(&self) -> Word {
+        let index = get_secret_number(0, self.words.len() - 1);
+        self.words[index].clone()
+    }
+
+    /// Parses user input into a word from the dictionary.
+    ///
+    /// Case and accents are ignored, so "APPLE" and "Ápple" both give "apple".
+    ///
+    /// # Arguments
+    /// * `input` - The user input to parse.
+    ///
+    /// # Returns
+    /// Returns a `Result<Word, GuessError<String>>`:
+    /// - `Ok(Word)` with the word as the dictionary writes it.
+    /// - `Err(GuessError::Empty)` if the input is blank.
+    /// - `Err(GuessError::Invalid)` if the word is not in the dictionary.
+    pub fn parse(&self, input: &str) -> Result<Word, GuessError<String>> {
+        let key = fold(input);
+        if key.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        match self
+            .words
+            .binary_search_by(|word| word.key.as_str().cmp(&key))
+        {
+            Ok(index) => Ok(self.words[index].clone()),
+            Err(_) => Err(GuessError::Invalid {
+                input: input.trim().to_string(),
+                kind: InputKind::Word,
+            }),
+        }
+    }
+
+    /// Parses a line typed by the player into a command or a word.
+    ///
+    /// Lines starting with a colon are commands (see `Command::parse`); anything else is parsed
+    /// by `Dictionary::parse`.
+    pub fn parse_player_input(&self, line: &str) -> Result<WordInput, GuessError<String>> {
+        if let Some(command) = Command::parse(line) {
+            return command.map(WordInput::Command);
+        }
+
+        self.parse(line).map(WordInput::Guess)
+    }
+}
+
+/// Enum to represent where the words of word mode come from.
+///
+/// # Variants
+///
+/// - `Builtin`: The built-in list of common English words, chosen with `--word`.
+/// - `File(PathBuf)`: A file with one word per line, chosen with `--dictionary`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WordList {
+    Builtin,
+    File(PathBuf),
+}
+
+impl WordList {
+    /// Loads the words.
+    ///
+    /// # Errors
+    /// Returns any error raised while reading the file.
+    pub fn load(&self) -> io::Result<Dictionary> {
+        match self {
+            WordList::Builtin => Ok(Dictionary::builtin()),
+            WordList::File(path) => Dictionary::load(path),
+        }
+    }
+}
+
+/// Plays one game with a secret word and returns how it ended.
+///
+/// This works like `classic::play`, except that every guess must be a word from the
+/// dictionary, and the feedback says whether the secret word comes before or after it
+/// alphabetically. Only the `:q` command is available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `dictionary` - The words the secret word is drawn from, which is never empty.
+pub fn play<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    dictionary: &Dictionary,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    let size = dictionary.len() as u64;
+    prompter.say(&ui.plural(Role::Info, "word-intro", size, &[]));
+
+    let secret = dictionary.secret();
+    let mut guess_count = GuessCount::new();
+
+    let outcome = loop {
+        let input = match prompter.next(|line| dictionary.parse_player_input(line)) {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let guess = match input {
+            WordInput::Guess(guess) => guess,
+            WordInput::Command(Command::Quit) => break GameOutcome::Quit,
+            WordInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+        let word = guess.clone();
+
+        guess_count.increment();
+        match handle_guess(guess, &secret) {
+            GuessResult::TooSmall => {
+                prompter.say(&ui.format(Role::TooSmall, "word-after", &[("word", &word)]))
+            }
+            GuessResult::TooBig => {
+                prompter.say(&ui.format(Role::TooBig, "word-before", &[("word", &word)]))
+            }
+            GuessResult::Correct => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                break GameOutcome::Won;
+            }
+        }
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(Role::Headline, "reveal-word", count, &[("secret", &secret)]));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{handle_guess, GuessResult};
+
+    // Test that case, whitespace and accents are folded away
+    #[test]
+    fn normalize_and_fold() {
+        assert_eq!(normalize("  Ice   Cream \n"), "ice cream");
+        assert_eq!(fold("Été"), "ete");
+        assert_eq!(fold("Straße"), "strasse");
+        assert_eq!(fold("ŒUVRE"), "oeuvre");
+        assert_eq!(Word::new("Élan").text(), "élan");
+    }
+
+    // Test reading a dictionary from text
+    #[test]
+    fn from_text_sorts_and_dedups() {
+        let dictionary = Dictionary::from_text("# fruit\nPear\n\napple\nÉlan\nAPPLE\n  fig \n");
+        let words: Vec<&str> = dictionary.words().iter().map(Word::text).collect();
+        assert_eq!(words, ["apple", "élan", "fig", "pear"]);
+        assert_eq!(dictionary.len(), 4);
+        assert!(Dictionary::from_text("# nothing\n").is_empty());
+    }
+
+    // Test that only dictionary words can be guessed
+    #[test]
+    fn parse_membership() {
+        let dictionary = Dictionary::from_text("apple\nélan\nfig\n");
+        assert_eq!(dictionary.parse(" APPLE\n"), Ok(Word::new("apple")));
+        assert_eq!(dictionary.parse("elan"), Ok(Word::new("élan")));
+        assert_eq!(dictionary.parse("   "), Err(GuessError::Empty));
+        assert_eq!(
+            dictionary.parse("Kiwi"),
+            Err(GuessError::Invalid {
+                input: "Kiwi".to_string(),
+                kind: InputKind::Word
+            })
+        );
+        assert_eq!(
+            dictionary.parse_player_input(":q"),
+            Ok(WordInput::Command(Command::Quit))
+        );
+        assert_eq!(Word::parse_input("Zebra"), Ok(Word::new("zebra")));
+    }
+
+    // Test that guesses compare alphabetically with the secret word
+    #[test]
+    fn alphabetical_feedback() {
+        let secret = Word::new("mango");
+        assert_eq!(
+            handle_guess(Word::new("apple"), &secret),
+            GuessResult::TooSmall
+        );
+        assert_eq!(
+            handle_guess(Word::new("Pear"), &secret),
+            GuessResult::TooBig
+        );
+        assert_eq!(
+            handle_guess(Word::new("MANGO"), &secret),
+            GuessResult::Correct
+        );
+        assert_eq!(
+            handle_guess(Word::new("élan"), &Word::new("engine")),
+            GuessResult::TooSmall
+        );
+    }
+
+    // Test the built-in word list and drawing a secret word from it
+    #[test]
+    fn builtin_dictionary() {
+        let dictionary = Dictionary::builtin();
+        assert!(dictionary.len() > 100);
+        assert!(dictionary
+            .words()
+            .windows(2)
+            .all(|w| w[0].key() < w[1].key()));
+        for _ in 0..20 {
+            assert!(dictionary.words().contains(&dictionary.secret()));
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    command::Command, integer::Integer, parse_player_input, prompt::Prompter, theme::Role,
+    GameOutcome, Guess, GuessCount, GuessResult, Incrementable, PlayerInput,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::io::{BufRead, Write};
+
+/// The largest drift that can be chosen, since the secret moves one step at a time.
+pub const MAX_DRIFT: u32 = 1000;
+
+/// Enum to represent which position of a moving secret the feedback for a guess refers to.
+///
+/// # Variants
+///
+/// - `Old`: The guess is compared with where the secret was when the guess was made, and the
+///   secret moves afterwards.
+/// - `New`: The secret moves first, and the guess is compared with where it ends up.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DriftFeedback {
+    #[default]
+    Old,
+    New,
+}
+
+impl DriftFeedback {
+    /// The names the feedback settings can be chosen by.
+    pub const NAMES: [&'static str; 2] = ["old", "new"];
+
+    /// Looks up a feedback setting by name, returning `None` if there is none by that name.
+    pub fn from_name(name: &str) -> Option<DriftFeedback> {
+        match name {
+            "old" => Some(DriftFeedback::Old),
+            "new" => Some(DriftFeedback::New),
+            _ => None,
+        }
+    }
+}
+
+/// A secret number that drifts by a random step after every guess.
+///
+/// Each step is drawn uniformly from `-drift..=drift` and the secret stops at the edges of the
+/// range, so it never leaves `min..=max`. The steps come from a random number generator that
+/// can be seeded, which makes a whole game repeatable. Every position the secret takes is
+/// recorded, so the trajectory can be shown when the game ends.
+///
+/// # Fields
+/// - `min`, `max`: The range the secret stays in.
+/// - `drift`: The largest step the secret can take after a guess.
+/// - `feedback`: Which position a guess is compared with.
+/// - `trajectory`: Every position the secret has taken, from the first to the current one.
+/// - `rng`: The source of the steps.
+#[derive(Debug)]
+pub struct MovingSecret<T = u32> {
+    min: T,
+    max: T,
+    drift: u32,
+    feedback: DriftFeedback,
+    trajectory: Vec<T>,
+    rng: StdRng,
+}
+
+impl<T: Integer> MovingSecret<T> {
+    /// Creates a moving secret starting at `start`.
+    ///
+    /// # Arguments
+    /// * `start` - The first position of the secret, in the range.
+    /// * `min`, `max` - The range the secret stays in.
+    /// * `drift` - The largest step the secret can take after a guess.
+    /// * `feedback` - Which position a guess is compared with.
+    /// * `seed` - The seed of the steps, or `None` for different steps every game.
+    pub fn new(
+        start: T,
+        min: T,
+        max: T,
+        drift: u32,
+        feedback: DriftFeedback,
+        seed: Option<u64>,
+    ) -> MovingSecret<T> {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        MovingSecret {
+            min,
+            max,
+            drift,
+            feedback,
+            trajectory: vec![start],
+            rng,
+        }
+    }
+
+    /// Creates a moving secret starting at a random position, drawn from the same seeded
+    /// generator as the steps.
+    pub fn random(
+        min: T,
+        max: T,
+        drift: u32,
+        feedback: DriftFeedback,
+        seed: Option<u64>,
+    ) -> MovingSecret<T> {
+        let mut secret = MovingSecret::new(min.clone(), min, max, drift, feedback, seed);
+        secret.trajectory[0] = secret
+            .rng
+            .gen_range(secret.min.clone()..=secret.max.clone());
+        secret
+    }
+
+    /// Returns where the secret is now.
+    pub fn position(&self) -> &T {
+        self.trajectory
+            .last()
+            .expect("a moving secret has a position")
+    }
+
+    /// Returns every position the secret has taken, from the first to the current one.
+    pub fn trajectory(&self) -> &[T] {
+        &self.trajectory
+    }
+
+    /// Makes a guess and returns its feedback.
+    ///
+    /// With `DriftFeedback::Old`, the guess is compared with the current position and the
+    /// secret moves unless it was found. With `DriftFeedback::New`, the secret moves first and
+    /// the guess is compared with its new position. Either way, `GuessResult::Correct` means
+    /// the secret was caught and does not move again.
+    pub fn guess(&mut self, guess: Guess<T>) -> GuessResult {
+        let value = guess.value();
+        match self.feedback {
+            DriftFeedback::Old => {
+                let result = GuessResult::from(value.cmp(self.position()));
+                if result != GuessResult::Correct {
+                    self.drift();
+                }
+                result
+            }
+            DriftFeedback::New => {
+                self.drift();
+                GuessResult::from(value.cmp(self.position()))
+            }
+        }
+    }
+
+    /// Moves the secret by a random step and records its new position.
+    fn drift(&mut self) {
+        let drift = i64::from(self.drift);
+        let step = self.rng.gen_range(-drift..=drift);
+        let mut position = self.position().clone();
+        for _ in 0..step.unsigned_abs() {
+            let next = if step < 0 {
+                position.predecessor().filter(|next| *next >= self.min)
+            } else {
+                position.successor().filter(|next| *next <= self.max)
+            };
+            match next {
+                Some(next) => position = next,
+                None => break,
+            }
+        }
+        self.trajectory.push(position);
+    }
+}
+
+/// Plays one game with a secret number that moves after every guess and returns how it ended.
+///
+/// This works like `classic::play`, except that the secret number takes a random step of up
+/// to `drift` after every guess that does not catch it, staying in the range. Whether the
+/// feedback refers to where the secret was or where it moved to is chosen with
+/// `--drift-feedback`. The path the secret took is shown when the game ends. Only the `:q`
+/// command is available, since the history of a moving secret says little about where it is
+/// now.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `drift` - The largest step the secret takes after a guess.
+/// * `feedback` - Whether a guess is compared with where the secret was or where it moved to.
+/// * `seed` - The seed of the secret's start and steps, or `None` for a random one.
+/// * `min`, `max` - The range the secret number stays in.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    drift: u32,
+    feedback: DriftFeedback,
+    seed: Option<u64>,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    let intro = match feedback {
+        DriftFeedback::Old => "drift-intro",
+        DriftFeedback::New => "drift-intro-new",
+    };
+    prompter.say(&ui.format(Role::Info, intro, &[("drift", &drift)]));
+
+    let mut secret = MovingSecret::random(min.clone(), max.clone(), drift, feedback, seed);
+    let mut guess_count = GuessCount::new();
+
+    let outcome = loop {
+        let input =
+            match prompter.next(|line| parse_player_input(line, min.clone(), max.clone(), None)) {
+                Ok(input) => input,
+                Err(outcome) => break outcome,
+            };
+
+        let guess = match input {
+            PlayerInput::Guess(guess) => guess,
+            PlayerInput::Command(Command::Quit) => break GameOutcome::Quit,
+            PlayerInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+
+        guess_count.increment();
+        match secret.guess(guess) {
+            GuessResult::TooSmall => prompter.say(&ui.text(Role::TooSmall, "too-small")),
+            GuessResult::TooBig => prompter.say(&ui.text(Role::TooBig, "too-big")),
+            GuessResult::Correct => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                break GameOutcome::Won;
+            }
+        }
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(
+            Role::Headline,
+            "reveal",
+            count,
+            &[("secret", secret.position())],
+        ));
+    }
+    let path: Vec<String> = secret.trajectory().iter().map(T::to_string).collect();
+    let path = path.join(" → ");
+    prompter.say(&ui.format(Role::Info, "trajectory", &[("path", &path)]));
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn guess(value: i64) -> Guess<i64> {
+        Guess::in_range(value, 1, 100).unwrap()
+    }
+
+    // Test that the secret never moves more than the drift or leaves the range
+    #[test]
+    fn drift_stays_in_bounds() {
+        let mut secret = MovingSecret::new(3i64, 1, 10, 4, DriftFeedback::Old, None);
+        for _ in 0..200 {
+            secret.guess(Guess::in_range(0, 0, 100).unwrap());
+        }
+        let trajectory = secret.trajectory();
+        assert_eq!(trajectory.len(), 201);
+        assert!(trajectory.iter().all(|value| (1..=10).contains(value)));
+        assert!(trajectory.windows(2).all(|w| (w[0] - w[1]).abs() <= 4));
+    }
+
+    // Test that the same seed gives the same game
+    #[test]
+    fn seeded_games_repeat() {
+        let play = |seed| {
+            let mut secret = MovingSecret::random(1, 1000, 20, DriftFeedback::Old, Some(seed));
+            for _ in 0..10 {
+                secret.guess(guess(1));
+            }
+            secret.trajectory().to_vec()
+        };
+        assert_eq!(play(7), play(7));
+        assert_ne!(play(7), play(8));
+    }
+
+    // Test which position the feedback refers to
+    #[test]
+    fn feedback_position() {
+        let mut old = MovingSecret::new(50, 1, 100, 5, DriftFeedback::Old, Some(1));
+        assert_eq!(old.guess(guess(40)), GuessResult::TooSmall);
+        assert_eq!(old.trajectory().len(), 2);
+        let position = *old.position();
+        assert_eq!(old.guess(guess(position)), GuessResult::Correct);
+        assert_eq!(old.trajectory().len(), 2);
+
+        let mut new = MovingSecret::new(50, 1, 100, 5, DriftFeedback::New, Some(1));
+        let result = new.guess(guess(50));
+        assert_eq!(new.trajectory().len(), 2);
+        assert_eq!(result, GuessResult::from(50.cmp(new.position())));
+    }
+
+    // Test that big integers drift too
+    #[test]
+    fn big_integers_drift() {
+        let min = BigInt::from(10).pow(30u32);
+        let max: BigInt = &min + 10;
+        let mut secret = MovingSecret::new(
+            min.clone(),
+            min.clone(),
+            max.clone(),
+            3,
+            DriftFeedback::New,
+            None,
+        );
+        for _ in 0..20 {
+            secret.guess(Guess::in_range(min.clone(), min.clone(), max.clone()).unwrap());
+        }
+        assert!(secret
+            .trajectory()
+            .iter()
+            .all(|value| (&min..=&max).contains(&value)));
+    }
+}
@@ -0,0 +1,277 @@
+/// The deepest an expression can nest parentheses, minus signs and powers, so that input
+/// like a million minus signs is refused instead of overflowing the stack.
+pub const MAX_DEPTH: usize = 256;
+
+/// Enum to represent why an arithmetic expression could not be evaluated.
+///
+/// # Variants
+///
+/// - `Syntax`: The expression is not well-formed, e.g. `3+` or `(4`, or it nests deeper than
+///   `MAX_DEPTH`.
+/// - `DivisionByZero`: The expression divides by zero.
+/// - `Overflow`: A step of the calculation is too large to compute.
+/// - `NoPreviousGuess`: The expression uses `last` before any guess has been made.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExprError {
+    Syntax,
+    DivisionByZero,
+    Overflow,
+    NoPreviousGuess,
+}
+
+/// Checks whether `input` should be treated as an arithmetic expression.
+///
+/// Expressions start with a digit, an opening parenthesis or the word `last`, optionally
+/// after a minus sign. Anything else, such as a number written in words, is left to the other
+/// ways of reading a guess.
+pub fn looks_like_expression(input: &str) -> bool {
+    let mut input = input.trim_start();
+    while let Some(rest) = input.strip_prefix('-') {
+        input = rest.trim_start();
+    }
+    input.starts_with(|c: char| c.is_ascii_digit() || c == '(')
+        || input
+            .get(..4)
+            .is_some_and(|word| word.eq_ignore_ascii_case("last"))
+}
+
+/// Evaluates an arithmetic expression over whole numbers.
+///
+/// The expression may use `+`, `-`, `*`, `/` (division rounds towards zero), `^` (power),
+/// parentheses, unary minus and the word `last`, which stands for the previous guess.
+/// The usual precedence rules apply: `^` binds tightest and is right-associative, then
+/// `*` and `/`, then `+` and `-`. Every step is checked, so nothing ever wraps around.
+///
+/// # Arguments
+/// * `input` - The expression, e.g. `(37+62)/2`, `2^6` or `last+10`.
+/// * `last` - The value of the previous guess, if there was one.
+///
+/// # Returns
+/// Returns a `Result`:
+/// - `Ok(i64)` with the value of the expression.
+/// - `Err(ExprError)` describing why the expression could not be evaluated.
+pub fn evaluate(input: &str, last: Option<i64>) -> Result<i64, ExprError> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        position: 0,
+        last,
+        depth: 0,
+    };
+    let value = parser.expression()?;
+    parser.skip_whitespace();
+    if parser.position != parser.chars.len() {
+        return Err(ExprError::Syntax);
+    }
+    Ok(value)
+}
+
+/// A recursive-descent parser that evaluates an expression as it reads it.
+///
+/// # Fields
+/// - `chars`: The characters of the expression.
+/// - `position`: The index of the next character to read.
+/// - `last`: The value `last` stands for.
+/// - `depth`: How many nested rules are being parsed, up to `MAX_DEPTH`.
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    last: Option<i64>,
+    depth: usize,
+}
+
+impl Parser {
+    /// Parses one nested part of the expression with `rule`, failing with `ExprError::Syntax`
+    /// once the nesting is deeper than `MAX_DEPTH`.
+    fn nested(
+        &mut self,
+        rule: fn(&mut Parser) -> Result<i64, ExprError>,
+    ) -> Result<i64, ExprError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(ExprError::Syntax);
+        }
+        self.depth += 1;
+        let value = rule(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|c| c.is_whitespace())
+        {
+            self.position += 1;
+        }
+    }
+
+    /// Skips whitespace and consumes `expected` if it is the next character.
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.chars.get(self.position) == Some(&expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<i64, ExprError> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value = value.checked_add(self.term()?).ok_or(ExprError::Overflow)?;
+            } else if self.eat('-') {
+                value = value.checked_sub(self.term()?).ok_or(ExprError::Overflow)?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<i64, ExprError> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat('*') {
+                value = value
+                    .checked_mul(self.unary()?)
+                    .ok_or(ExprError::Overflow)?;
+            } else if self.eat('/') {
+                let divisor = self.unary()?;
+                if divisor == 0 {
+                    return Err(ExprError::DivisionByZero);
+                }
+                value = value.checked_div(divisor).ok_or(ExprError::Overflow)?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// unary := '-' unary | power
+    fn unary(&mut self) -> Result<i64, ExprError> {
+        if self.eat('-') {
+            return self
+                .nested(Parser::unary)?
+                .checked_neg()
+                .ok_or(ExprError::Overflow);
+        }
+        self.power()
+    }
+
+    /// power := atom ('^' unary)?
+    fn power(&mut self) -> Result<i64, ExprError> {
+        let base = self.atom()?;
+        if !self.eat('^') {
+            return Ok(base);
+        }
+        let exponent = self.nested(Parser::unary)?;
+        // A negative exponent would give a fraction, which is not a whole number.
+        let exponent = u32::try_from(exponent).map_err(|_| match exponent {
+            e if e < 0 => ExprError::Syntax,
+            _ => ExprError::Overflow,
+        })?;
+        base.checked_pow(exponent).ok_or(ExprError::Overflow)
+    }
+
+    /// atom := number | 'last' | '(' expression ')'
+    fn atom(&mut self) -> Result<i64, ExprError> {
+        if self.eat('(') {
+            let value = self.nested(Parser::expression)?;
+            if !self.eat(')') {
+                return Err(ExprError::Syntax);
+            }
+            return Ok(value);
+        }
+
+        self.skip_whitespace();
+        let start = self.position;
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|c| c.is_ascii_alphanumeric())
+        {
+            self.position += 1;
+        }
+        let word: String = self.chars[start..self.position].iter().collect();
+
+        if word.eq_ignore_ascii_case("last") {
+            return self.last.ok_or(ExprError::NoPreviousGuess);
+        }
+        if word.is_empty() || !word.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ExprError::Syntax);
+        }
+        word.parse::<i64>().map_err(|_| ExprError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test the examples players are expected to type
+    #[test]
+    fn evaluate_examples() {
+        assert_eq!(evaluate("(37+62)/2", None), Ok(49));
+        assert_eq!(evaluate("2^6", None), Ok(64));
+        assert_eq!(evaluate("last+10", Some(40)), Ok(50));
+        assert_eq!(evaluate(" LAST - 5 ", Some(40)), Ok(35));
+    }
+
+    // Test operator precedence and associativity
+    #[test]
+    fn evaluate_precedence() {
+        assert_eq!(evaluate("2+3*4", None), Ok(14));
+        assert_eq!(evaluate("(2+3)*4", None), Ok(20));
+        assert_eq!(evaluate("2^3^2", None), Ok(512));
+        assert_eq!(evaluate("-2^2", None), Ok(-4));
+        assert_eq!(evaluate("100-10-5", None), Ok(85));
+        assert_eq!(evaluate("100/10/5", None), Ok(2));
+    }
+
+    // Test that each kind of failure is reported
+    #[test]
+    fn evaluate_errors() {
+        assert_eq!(evaluate("10/(5-5)", None), Err(ExprError::DivisionByZero));
+        assert_eq!(evaluate("2^64", None), Err(ExprError::Overflow));
+        assert_eq!(
+            evaluate("99999999999*99999999999", None),
+            Err(ExprError::Overflow)
+        );
+        assert_eq!(evaluate("last+1", None), Err(ExprError::NoPreviousGuess));
+        assert_eq!(evaluate("3+", None), Err(ExprError::Syntax));
+        assert_eq!(evaluate("(4", None), Err(ExprError::Syntax));
+        assert_eq!(evaluate("4)", None), Err(ExprError::Syntax));
+        assert_eq!(evaluate("2^-1", None), Err(ExprError::Syntax));
+        assert_eq!(evaluate("12abc", None), Err(ExprError::Syntax));
+    }
+
+    // Test that deeply nested expressions are refused instead of overflowing the stack
+    #[test]
+    fn evaluate_deep_nesting() {
+        let minus = format!("{}5", "-".repeat(2_000_000));
+        assert!(looks_like_expression(&minus));
+        assert_eq!(evaluate(&minus, None), Err(ExprError::Syntax));
+        let parens = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert_eq!(evaluate(&parens, None), Err(ExprError::Syntax));
+        let powers = format!("2{}", "^1".repeat(100_000));
+        assert_eq!(evaluate(&powers, None), Err(ExprError::Syntax));
+        let shallow = format!("{}7{}", "(".repeat(100), ")".repeat(100));
+        assert_eq!(evaluate(&shallow, None), Ok(7));
+        assert_eq!(evaluate("--5", None), Ok(5));
+    }
+
+    // Test which inputs are treated as expressions
+    #[test]
+    fn looks_like_expression_rules() {
+        assert!(looks_like_expression("(1+2)"));
+        assert!(looks_like_expression("3*4"));
+        assert!(looks_like_expression("Last+1"));
+        assert!(!looks_like_expression("forty-two"));
+        assert!(!looks_like_expression("la"));
+        assert!(looks_like_expression("-5+3"));
+        assert!(!looks_like_expression("-"));
+    }
+}
@@ -0,0 +1,388 @@
+use crate::{
+    command::Command,
+    get_secret_number, handle_query,
+    integer::{Integer, Primitive},
+    prompt::Prompter,
+    theme::Role,
+    Answer, GameOutcome, Guess, GuessCount, GuessError, Incrementable, InputKind, Parsable, Query,
+};
+use num_bigint::BigUint;
+use std::{
+    fmt,
+    io::{BufRead, Write},
+};
+
+/// A set of numbers, written like `{3, 7, 10-20}`, that the player asks about in group-testing
+/// mode.
+///
+/// The set is stored as sorted, non-overlapping ranges, so even a set like `{1-1000000}` takes
+/// almost no space. Neighbouring ranges are merged, so `{1-5, 6, 7-9}` and `{1-9}` are the same
+/// set.
+///
+/// # Fields
+/// - `ranges`: The ranges of the set, each from its smallest to its largest number, sorted
+///   and with a gap between any two of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberSet<T = u32> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Integer> NumberSet<T> {
+    /// Creates the set of the numbers in any of the given ranges, each from its smallest to its
+    /// largest number. Ranges whose start is greater than their end are empty.
+    pub fn new(mut ranges: Vec<(T, T)>) -> NumberSet<T> {
+        ranges.retain(|(low, high)| low <= high);
+        ranges.sort();
+        let mut merged: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+        for (low, high) in ranges {
+            if let Some((_, last)) = merged.last_mut() {
+                // Ranges that overlap or touch become one, and the successor of the largest
+                // value of the type does not exist, so nothing can come after it.
+                if last.successor().is_none_or(|next| low <= next) {
+                    if high > *last {
+                        *last = high;
+                    }
+                    continue;
+                }
+            }
+            merged.push((low, high));
+        }
+        NumberSet { ranges: merged }
+    }
+
+    /// Creates the set holding only `value`.
+    pub fn single(value: T) -> NumberSet<T> {
+        NumberSet {
+            ranges: vec![(value.clone(), value)],
+        }
+    }
+
+    /// Returns the ranges of the set, sorted and with a gap between any two of them.
+    pub fn ranges(&self) -> &[(T, T)] {
+        &self.ranges
+    }
+
+    /// Checks whether `value` is in the set.
+    pub fn contains(&self, value: &T) -> bool {
+        // The ranges are sorted, so the only candidate is the last one starting at or before
+        // `value`.
+        let index = self.ranges.partition_point(|(low, _)| low <= value);
+        index > 0 && *value <= self.ranges[index - 1].1
+    }
+
+    /// Returns how many numbers are in the set.
+    pub fn len(&self) -> BigUint {
+        self.ranges
+            .iter()
+            .map(|(low, high)| T::distance(low, high) + 1u32)
+            .sum()
+    }
+
+    /// Returns `true` if the set has no numbers.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the only number in the set, or `None` if it has none or several.
+    pub fn only(&self) -> Option<T> {
+        match self.ranges.as_slice() {
+            [(low, high)] if low == high => Some(low.clone()),
+            _ => None,
+        }
+    }
+
+    /// Parses a set of numbers in the range `min..=max`.
+    ///
+    /// A set is written between braces, with its members separated by commas. Each member is
+    /// a number, written in any way `Guess::parse_in_range` accepts, or a range of numbers like
+    /// `10-20` that includes both ends. A dash right after a digit always starts the end of a
+    /// range, and negative numbers can be used in ranges too, as in `-5--1`.
+    ///
+    /// # Arguments
+    /// * `input` - The user input to parse.
+    /// * `min`, `max` - The smallest and largest numbers a set may hold.
+    ///
+    /// # Returns
+    /// Returns a `Result<NumberSet<T>, GuessError<T>>`:
+    /// - `Ok(NumberSet)` with the numbers of the set.
+    /// - `Err(GuessError::Empty)` if the input is blank.
+    /// - `Err(GuessError::Invalid)` if the braces, commas or ranges are not well-formed.
+    /// - Any error of `Guess::parse_in_range` for a member that is not a number in the range.
+    pub fn parse(input: &str, min: T, max: T) -> Result<NumberSet<T>, GuessError<T>> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        let invalid = || GuessError::Invalid {
+            input: input.to_string(),
+            kind: InputKind::Set,
+        };
+        let inner = input
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .ok_or_else(invalid)?;
+        if inner.trim().is_empty() {
+            return Ok(NumberSet::new(Vec::new()));
+        }
+
+        let number = |text: &str| {
+            if text.trim().is_empty() {
+                return Err(invalid());
+            }
+            Guess::parse_in_range(text, min.clone(), max.clone(), None).map(|guess| guess.value())
+        };
+        let mut ranges = Vec::new();
+        for member in inner.split(',') {
+            let range = match split_range(member) {
+                Some((low, high)) => (number(low)?, number(high)?),
+                None => {
+                    let value = number(member)?;
+                    (value.clone(), value)
+                }
+            };
+            if range.0 > range.1 {
+                return Err(invalid());
+            }
+            ranges.push(range);
+        }
+        Ok(NumberSet::new(ranges))
+    }
+}
+
+/// Splits a member of a set like `10-20` into the two ends of the range, or returns `None` if
+/// the member is a single number.
+///
+/// The dash of a range comes right after a digit, so a leading minus sign or the dash in a
+/// number word like "twenty-one" is never mistaken for one.
+fn split_range(member: &str) -> Option<(&str, &str)> {
+    let member = member.trim();
+    let dash = member
+        .char_indices()
+        .skip(1)
+        .find(|&(index, c)| {
+            c == '-'
+                && member[..index]
+                    .trim_end()
+                    .ends_with(|c: char| c.is_ascii_digit())
+        })
+        .map(|(index, _)| index)?;
+    Some((&member[..dash], &member[dash + 1..]))
+}
+
+impl<T: fmt::Display + PartialEq> fmt::Display for NumberSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+        for (index, (low, high)) in self.ranges.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            if low == high {
+                write!(f, "{}", low)?;
+            } else {
+                write!(f, "{}-{}", low, high)?;
+            }
+        }
+        f.write_str("}")
+    }
+}
+
+/// Trait implementation for `NumberSet` to ask whether it contains the secret number.
+impl<T: Integer> Query<T> for NumberSet<T> {
+    fn matches(&self, secret: &T) -> bool {
+        self.contains(secret)
+    }
+}
+
+/// Trait implementation for `NumberSet` to make it parsable from a string input.
+///
+/// The input is parsed by `NumberSet::parse` with the numbers of the classic game, 1 to 100.
+impl<T: Primitive> Parsable for NumberSet<T> {
+    type Value = T;
+
+    fn parse_input(input: &str) -> Result<NumberSet<T>, GuessError<T>> {
+        NumberSet::parse(input, T::DEFAULT_MIN, T::DEFAULT_MAX)
+    }
+}
+
+/// Enum to represent one line of input from the player in group-testing mode.
+///
+/// # Variants
+///
+/// - `Query(NumberSet<T>)`: The player asked whether the secret number is in a set. A plain
+///   number asks about the set holding only that number.
+/// - `Command(Command)`: The player typed a command such as `:q`.
+#[derive(Debug, PartialEq)]
+pub enum GroupInput<T = u32> {
+    Query(NumberSet<T>),
+    Command(Command),
+}
+
+/// Parses a line typed by the player into a command or a set of numbers in `min..=max`.
+///
+/// Lines starting with a colon are commands (see `Command::parse`), lines starting with a
+/// brace are sets (see `NumberSet::parse`), and anything else is a single number parsed by
+/// `Guess::parse_in_range`.
+pub fn parse_group_input<T: Integer>(
+    line: &str,
+    min: T,
+    max: T,
+) -> Result<GroupInput<T>, GuessError<T>> {
+    if let Some(command) = Command::parse(line) {
+        return command.map(GroupInput::Command);
+    }
+    if line.trim_start().starts_with('{') {
+        return NumberSet::parse(line, min, max).map(GroupInput::Query);
+    }
+
+    Guess::parse_in_range(line, min, max, None)
+        .map(|guess| GroupInput::Query(NumberSet::single(guess.value())))
+}
+
+/// Plays one game of group testing and returns how it ended.
+///
+/// Instead of guessing the secret number, the player asks whether it is in a set of numbers
+/// like `{3, 7, 10-20}`, and is answered yes or no by `handle_query`. Every question counts
+/// as a guess. A plain number asks about the set holding only that number, and the game is
+/// won once the player asks about a set of one number that holds the secret. Only the `:q`
+/// command is available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `min`, `max` - The smallest and largest possible secret number.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    prompter.say(&ui.text(Role::Info, "group-intro"));
+
+    let secret = get_secret_number(min.clone(), max.clone());
+    let mut guess_count = GuessCount::new();
+
+    let outcome = loop {
+        let input = match prompter.next(|line| parse_group_input(line, min.clone(), max.clone())) {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let set = match input {
+            GroupInput::Query(set) => set,
+            GroupInput::Command(Command::Quit) => break GameOutcome::Quit,
+            GroupInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+
+        guess_count.increment();
+        match handle_query(&set, &secret) {
+            Answer::Yes if set.only().is_some() => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                break GameOutcome::Won;
+            }
+            Answer::Yes => prompter.say(&ui.format(Role::Info, "group-yes", &[("set", &set)])),
+            Answer::No => prompter.say(&ui.format(Role::Info, "group-no", &[("set", &set)])),
+        }
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(Role::Headline, "reveal", count, &[("secret", &secret)]));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{handle_query, Answer};
+
+    fn set(input: &str) -> NumberSet<i64> {
+        NumberSet::parse(input, -100, 100).unwrap()
+    }
+
+    // Test parsing sets with numbers and ranges
+    #[test]
+    fn parse_sets() {
+        assert_eq!(set("{3,7,10-20}").ranges(), [(3, 3), (7, 7), (10, 20)]);
+        assert_eq!(
+            set(" { 20 - 30 , five, 1-4 } ").ranges(),
+            [(1, 5), (20, 30)]
+        );
+        assert_eq!(set("{-5--1, -10}").ranges(), [(-10, -10), (-5, -1)]);
+        assert_eq!(set("{twenty-one}").ranges(), [(21, 21)]);
+        assert!(set("{}").is_empty());
+        assert_eq!(set("{1-50, 40-60, 61}").to_string(), "{1-61}");
+    }
+
+    // Test that badly written sets are rejected
+    #[test]
+    fn parse_invalid_sets() {
+        for input in ["{3,,7}", "{20-10}", "{3, 7", "3, 7}", "{1-}"] {
+            assert_eq!(
+                NumberSet::<i64>::parse(input, 1, 100),
+                Err(GuessError::Invalid {
+                    input: input.to_string(),
+                    kind: InputKind::Set
+                }),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            NumberSet::<i64>::parse("  ", 1, 100),
+            Err(GuessError::Empty)
+        );
+        assert_eq!(
+            NumberSet::<u32>::parse_input("{5, 150}"),
+            Err(GuessError::OutOfRange {
+                value: 150,
+                min: 1,
+                max: 100
+            })
+        );
+    }
+
+    // Test membership queries against the secret number
+    #[test]
+    fn membership() {
+        let query = set("{3, 7, 10-20}");
+        assert_eq!(handle_query(&query, &7), Answer::Yes);
+        assert_eq!(handle_query(&query, &15), Answer::Yes);
+        assert_eq!(handle_query(&query, &8), Answer::No);
+        assert_eq!(handle_query(&query, &21), Answer::No);
+        assert_eq!(handle_query(&query, &1), Answer::No);
+        assert_eq!(query.len(), BigUint::from(13u32));
+        assert_eq!(query.only(), None);
+        assert_eq!(set("{42}").only(), Some(42));
+    }
+
+    // Test the sets at the very ends of a type
+    #[test]
+    fn sets_at_type_limits() {
+        let full = NumberSet::new(vec![(0u8, 200), (150, 255)]);
+        assert_eq!(full.ranges(), [(0, 255)]);
+        assert_eq!(full.len(), BigUint::from(256u32));
+        assert!(full.contains(&255));
+    }
+
+    // Test reading queries and commands in group-testing mode
+    #[test]
+    fn parse_input() {
+        assert_eq!(
+            parse_group_input("42", 1, 100),
+            Ok(GroupInput::Query(NumberSet::single(42)))
+        );
+        assert_eq!(
+            parse_group_input("{1-10}", 1, 100),
+            Ok(GroupInput::Query(NumberSet::new(vec![(1, 10)])))
+        );
+        assert_eq!(
+            parse_group_input::<i64>(":q", 1, 100),
+            Ok(GroupInput::Command(Command::Quit))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{integer::Integer, interval::KnownInterval, GuessResult};
+
+/// A struct recording the guesses made in a round and the feedback they got.
+///
+/// The `History` struct backs the `:history`, `:range`, `:hint` and `:undo` commands. Every
+/// guess that is compared to the secret number is recorded, and the range the secret must
+/// lie in is worked out from the feedback with a `KnownInterval`.
+///
+/// # Fields
+/// - `min`, `max`: The range of the game, used before any feedback narrows it down.
+/// - `entries`: The guesses in the order they were made, with their results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct History<T = u32> {
+    min: T,
+    max: T,
+    entries: Vec<(T, GuessResult)>,
+}
+
+impl<T: Integer> History<T> {
+    /// Creates an empty history for a game played over `min..=max`.
+    pub fn new(min: T, max: T) -> History<T> {
+        History {
+            min,
+            max,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Records a guess and the feedback it got.
+    pub fn record(&mut self, value: T, result: GuessResult) {
+        self.entries.push((value, result));
+    }
+
+    /// Removes the most recent guess and returns it, or `None` if there are no guesses.
+    pub fn undo(&mut self) -> Option<(T, GuessResult)> {
+        self.entries.pop()
+    }
+
+    /// Returns the guesses made so far, oldest first.
+    pub fn entries(&self) -> &[(T, GuessResult)] {
+        &self.entries
+    }
+
+    /// Returns the value of the most recent guess, if any.
+    pub fn last_value(&self) -> Option<T> {
+        self.entries.last().map(|(value, _)| value.clone())
+    }
+
+    /// Returns what the feedback so far says about the secret number.
+    ///
+    /// The interval is rebuilt from the entries, so it always agrees with them, even after
+    /// `:undo`.
+    pub fn interval(&self) -> KnownInterval<T> {
+        let mut interval = KnownInterval::new(self.min.clone(), self.max.clone());
+        for (value, result) in &self.entries {
+            interval.record(value.clone(), *result);
+        }
+        interval
+    }
+
+    /// Returns the smallest and largest values the secret number can still be.
+    pub fn known_range(&self) -> (T, T) {
+        self.interval().bounds()
+    }
+
+    /// Suggests the next guess: the middle of the known range, as a binary search would.
+    pub fn hint(&self) -> T {
+        self.interval().midpoint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that feedback narrows the known range
+    #[test]
+    fn known_range_narrows() {
+        let mut history = History::new(1, 100);
+        assert_eq!(history.known_range(), (1, 100));
+        history.record(50, GuessResult::TooSmall);
+        history.record(75, GuessResult::TooBig);
+        assert_eq!(history.known_range(), (51, 74));
+        assert_eq!(history.hint(), 62);
+        assert_eq!(history.last_value(), Some(75));
+    }
+
+    // Test that undo forgets the last guess and its feedback
+    #[test]
+    fn undo_restores_range() {
+        let mut history = History::new(1, 100);
+        history.record(50, GuessResult::TooSmall);
+        history.record(75, GuessResult::TooBig);
+        assert_eq!(history.undo(), Some((75, GuessResult::TooBig)));
+        assert_eq!(history.known_range(), (51, 100));
+        assert_eq!(history.entries().len(), 1);
+        history.undo();
+        assert_eq!(history.undo(), None);
+    }
+}
@@ -0,0 +1,268 @@
+use std::{collections::HashMap, fmt::Display};
+
+/// Enum to represent the languages the game can be played in.
+///
+/// Each locale has a message catalog in the `locales` directory, which is embedded into the
+/// binary at compile time so the game never has to find files at runtime.
+///
+/// # Variants
+///
+/// - `English`: The default locale, used as the fallback for missing messages.
+/// - `Spanish`: Spanish messages.
+/// - `French`: French messages.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+    French,
+}
+
+impl Locale {
+    /// Every supported locale, in the order they are listed to the player.
+    pub const ALL: [Locale; 3] = [Locale::English, Locale::Spanish, Locale::French];
+
+    /// Returns the two-letter language code of the locale.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+            Locale::French => "fr",
+        }
+    }
+
+    /// Looks up a locale from a language tag.
+    ///
+    /// Only the language part of the tag is used, so `fr`, `fr_FR`, `fr-CA` and
+    /// `fr_FR.UTF-8` all select French.
+    ///
+    /// # Arguments
+    /// * `tag` - A language tag such as the value of the `LANG` environment variable.
+    ///
+    /// # Returns
+    /// Returns `Some(Locale)` if the language is supported and `None` otherwise.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code() == language)
+    }
+
+    /// Picks the locale from the environment.
+    ///
+    /// The variables `LC_ALL`, `LC_MESSAGES` and `LANG` are checked in that order, as the
+    /// C library does. The first variable that is set and names a supported language wins;
+    /// if none does, English is used.
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find_map(|value| Locale::from_tag(&value))
+            .unwrap_or_default()
+    }
+
+    /// Returns the plural category used for `count` in this locale.
+    ///
+    /// English and Spanish use the singular only for exactly one, while French also
+    /// uses it for zero.
+    pub fn plural_category(&self, count: u64) -> &'static str {
+        let singular = match self {
+            Locale::English | Locale::Spanish => count == 1,
+            Locale::French => count <= 1,
+        };
+        if singular {
+            "one"
+        } else {
+            "other"
+        }
+    }
+
+    /// Returns the character written between the whole and the fractional part of a
+    /// decimal number: a point in English, and a comma in Spanish and French.
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Locale::English => '.',
+            Locale::Spanish | Locale::French => ',',
+        }
+    }
+
+    /// Returns `true` if numeric dates put the month before the day, as in `03/15/2024`.
+    ///
+    /// This is the American English order; Spanish and French put the day first.
+    pub fn month_first(&self) -> bool {
+        matches!(self, Locale::English)
+    }
+
+    /// Returns the raw catalog file for this locale.
+    fn source(&self) -> &'static str {
+        match self {
+            Locale::English => include_str!("../locales/en.txt"),
+            Locale::Spanish => include_str!("../locales/es.txt"),
+            Locale::French => include_str!("../locales/fr.txt"),
+        }
+    }
+}
+
+/// Parses a catalog file into a map from message keys to messages.
+///
+/// Blank lines and lines starting with `#` are ignored; every other line must have the
+/// form `key = message`.
+fn parse_catalog(source: &'static str) -> HashMap<&'static str, &'static str> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, message)| (key.trim(), message.trim()))
+        .collect()
+}
+
+/// A struct holding the translated messages for one locale.
+///
+/// The `Catalog` struct looks messages up by key and fills in their placeholders. Any
+/// message missing from the selected locale falls back to English, and a message missing
+/// from English as well is shown as its key, so a gap in a translation never hides output.
+///
+/// # Fields
+/// - `locale`: The locale the messages belong to.
+/// - `messages`: The messages of the locale, keyed by message key.
+/// - `fallback`: The English messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catalog {
+    locale: Locale,
+    messages: HashMap<&'static str, &'static str>,
+    fallback: HashMap<&'static str, &'static str>,
+}
+
+impl Catalog {
+    /// Creates the catalog for the given locale.
+    pub fn new(locale: Locale) -> Catalog {
+        Catalog {
+            locale,
+            messages: parse_catalog(locale.source()),
+            fallback: parse_catalog(Locale::English.source()),
+        }
+    }
+
+    /// Returns the locale of the catalog.
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Returns the message for `key` without filling in any placeholders.
+    pub fn get(&self, key: &str) -> String {
+        self.messages
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map_or_else(|| key.to_string(), |message| message.to_string())
+    }
+
+    /// Returns the message for `key` with its placeholders filled in.
+    ///
+    /// # Arguments
+    /// * `key` - The message key.
+    /// * `args` - Pairs of placeholder names and values; `("count", &3)` replaces `{count}`.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut message = self.get(key);
+        for (name, value) in args {
+            message = message.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        message
+    }
+
+    /// Returns the plural form of the message for `key` that matches `count`.
+    ///
+    /// The plural forms are stored as `key.one` and `key.other`. The `count` is also
+    /// available to the message as the `{count}` placeholder.
+    pub fn plural(&self, key: &str, count: u64, args: &[(&str, &dyn Display)]) -> String {
+        let key = format!("{}.{}", key, self.locale.plural_category(count));
+        let mut all_args: Vec<(&str, &dyn Display)> = vec![("count", &count)];
+        all_args.extend_from_slice(args);
+        self.format(&key, &all_args)
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Catalog::new(Locale::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Returns the placeholders used in a message, sorted
+    fn placeholders(message: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    // Test that every English key is translated in every other locale
+    #[test]
+    fn every_key_is_translated() {
+        let english = parse_catalog(Locale::English.source());
+        for locale in Locale::ALL {
+            let messages = parse_catalog(locale.source());
+            for (key, message) in &english {
+                let translated = messages
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{} is missing '{}'", locale.code(), key));
+                assert_eq!(
+                    placeholders(translated),
+                    placeholders(message),
+                    "{} uses different placeholders for '{}'",
+                    locale.code(),
+                    key
+                );
+            }
+            for key in messages.keys() {
+                assert!(
+                    english.contains_key(key),
+                    "{} has unknown key '{}'",
+                    locale.code(),
+                    key
+                );
+            }
+        }
+    }
+
+    // Test that language tags from the environment are understood
+    #[test]
+    fn from_tag_variants() {
+        assert_eq!(Locale::from_tag("fr_FR.UTF-8"), Some(Locale::French));
+        assert_eq!(Locale::from_tag("es-MX"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_tag("EN"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("C"), None);
+        assert_eq!(Locale::from_tag(""), None);
+    }
+
+    // Test the plural forms of the win message
+    #[test]
+    fn plural_forms() {
+        let english = Catalog::new(Locale::English);
+        assert_eq!(english.plural("win", 1, &[]), "You win, in 1 guess!");
+        assert_eq!(english.plural("win", 4, &[]), "You win, in 4 guesses!");
+
+        let french = Catalog::new(Locale::French);
+        assert_eq!(french.plural("win", 0, &[]), "Gagné, en 0 essai !");
+        assert_eq!(french.plural("win", 2, &[]), "Gagné, en 2 essais !");
+    }
+
+    // Test that unknown keys fall back to the key itself
+    #[test]
+    fn missing_key_falls_back() {
+        let catalog = Catalog::new(Locale::Spanish);
+        assert_eq!(catalog.get("no-such-key"), "no-such-key");
+        assert_eq!(catalog.get("too-big"), "Demasiado grande");
+    }
+}
@@ -0,0 +1,245 @@
+use num_bigint::{BigInt, BigUint};
+use rand::distributions::uniform::SampleUniform;
+use std::{
+    fmt,
+    hash::Hash,
+    num::{IntErrorKind, ParseIntError},
+};
+
+/// Trait for the integer types a `Guess` can hold.
+///
+/// The `Integer` trait is implemented for every primitive integer type, from `i8` and `u8`
+/// up to `i128` and `u128`, and for `BigInt`, which has no size limit at all. It gathers what
+/// the game needs from a number: parsing it from digits, drawing it at random, comparing it,
+/// converting the numbers produced by words and expressions, and stepping and measuring
+/// ranges without ever overflowing.
+///
+/// # Associated Constants
+///
+/// - `SIGNED`: Whether the type can hold negative numbers.
+pub trait Integer:
+    Clone + Ord + Hash + fmt::Debug + fmt::Display + SampleUniform + Send + Sync + 'static
+{
+    const SIGNED: bool;
+
+    /// Parses a number written in digits, with an optional sign.
+    ///
+    /// The error is the one `str::parse` gives for primitive integers, so the reason a
+    /// number was rejected can be told the same way for every type.
+    fn parse_digits(input: &str) -> Result<Self, ParseIntError>;
+
+    /// Converts an `i64`, the type expressions are evaluated in, or returns `None` if it does
+    /// not fit.
+    fn from_i64(value: i64) -> Option<Self>;
+
+    /// Converts a `u32`, the type numbers written in words are read as, or returns `None` if
+    /// it does not fit.
+    fn from_u32(value: u32) -> Option<Self>;
+
+    /// Converts the value to an `i64` so expressions can use it, or returns `None` if it does
+    /// not fit.
+    fn to_i64(&self) -> Option<i64>;
+
+    /// Returns the next larger value, or `None` if `self` is the largest value of the type.
+    fn successor(&self) -> Option<Self>;
+
+    /// Returns the next smaller value, or `None` if `self` is the smallest value of the type.
+    fn predecessor(&self) -> Option<Self>;
+
+    /// Returns the value halfway between `low` and `high`, rounded down, without overflowing.
+    fn middle(low: &Self, high: &Self) -> Self;
+
+    /// Returns `high - low`, which can be larger than any value of the type itself.
+    /// `low` must not be greater than `high`.
+    fn distance(low: &Self, high: &Self) -> BigUint;
+}
+
+/// Trait for the primitive integer types, which are `Copy` and have a classic range.
+///
+/// # Associated Constants
+///
+/// - `DEFAULT_MIN`, `DEFAULT_MAX`: The range of the classic game, 1 to 100, in this type.
+pub trait Primitive: Integer + Copy {
+    const DEFAULT_MIN: Self;
+    const DEFAULT_MAX: Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty: $signed:literal),* $(,)?) => {$(
+        impl Integer for $t {
+            const SIGNED: bool = $signed;
+
+            fn parse_digits(input: &str) -> Result<Self, ParseIntError> {
+                input.parse()
+            }
+
+            fn from_i64(value: i64) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+
+            fn from_u32(value: u32) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(*self).ok()
+            }
+
+            fn successor(&self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn predecessor(&self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn middle(low: &Self, high: &Self) -> Self {
+                // The shared bits plus half of the differing bits is the average rounded
+                // down, and no step of it can overflow.
+                (low & high) + ((low ^ high) >> 1)
+            }
+
+            fn distance(low: &Self, high: &Self) -> BigUint {
+                // Both casts are lossless for the types involved, and the wrapping
+                // subtraction gives the right answer even for the full `i128` range.
+                BigUint::from((*high as i128 as u128).wrapping_sub(*low as i128 as u128))
+            }
+        }
+
+        impl Primitive for $t {
+            const DEFAULT_MIN: Self = 1;
+            const DEFAULT_MAX: Self = 100;
+        }
+    )*};
+}
+
+impl_integer!(
+    i8: true,
+    i16: true,
+    i32: true,
+    i64: true,
+    i128: true,
+    isize: true,
+    u8: false,
+    u16: false,
+    u32: false,
+    u64: false,
+    u128: false,
+    usize: false,
+);
+
+impl Integer for BigInt {
+    const SIGNED: bool = true;
+
+    fn parse_digits(input: &str) -> Result<Self, ParseIntError> {
+        // Going through `i128` gives the same errors as the primitive types for empty or
+        // malformed input; only numbers too long for it need the slower big parser.
+        match input.parse::<i128>() {
+            Ok(value) => Ok(BigInt::from(value)),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow
+                ) =>
+            {
+                input.parse().map_err(|_| e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn from_i64(value: i64) -> Option<Self> {
+        Some(BigInt::from(value))
+    }
+
+    fn from_u32(value: u32) -> Option<Self> {
+        Some(BigInt::from(value))
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        num_traits::ToPrimitive::to_i64(self)
+    }
+
+    fn successor(&self) -> Option<Self> {
+        Some(self + 1)
+    }
+
+    fn predecessor(&self) -> Option<Self> {
+        Some(self - 1)
+    }
+
+    fn middle(low: &Self, high: &Self) -> Self {
+        // Shifting a `BigInt` right rounds towards negative infinity, like the primitives.
+        (low + high) >> 1u32
+    }
+
+    fn distance(low: &Self, high: &Self) -> BigUint {
+        (high - low).to_biguint().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that the middle never overflows and rounds down
+    #[test]
+    fn middle_rounds_down() {
+        assert_eq!(u32::middle(&1, &100), 50);
+        assert_eq!(u8::middle(&254, &255), 254);
+        assert_eq!(i8::middle(&-128, &127), -1);
+        assert_eq!(i64::middle(&-500, &500), 0);
+        assert_eq!(i32::middle(&-5, &-2), -4);
+        assert_eq!(
+            BigInt::middle(&BigInt::from(-5), &BigInt::from(-2)),
+            BigInt::from(-4)
+        );
+    }
+
+    // Test distances across the full range of each kind of type
+    #[test]
+    fn distance_covers_full_range() {
+        assert_eq!(u8::distance(&0, &255), BigUint::from(255u32));
+        assert_eq!(i8::distance(&-128, &127), BigUint::from(255u32));
+        assert_eq!(i64::distance(&-500, &500), BigUint::from(1000u32));
+        assert_eq!(u128::distance(&0, &u128::MAX), BigUint::from(u128::MAX));
+        assert_eq!(
+            i128::distance(&i128::MIN, &i128::MAX),
+            BigUint::from(u128::MAX)
+        );
+        let googol = BigInt::from(10).pow(100);
+        assert_eq!(
+            BigInt::distance(&BigInt::from(0), &googol),
+            BigUint::from(10u32).pow(100)
+        );
+    }
+
+    // Test conversions from words and expressions
+    #[test]
+    fn conversions() {
+        assert_eq!(u8::from_u32(200), Some(200));
+        assert_eq!(i8::from_u32(200), None);
+        assert_eq!(u32::from_i64(-1), None);
+        assert_eq!(i16::from_i64(-1), Some(-1));
+        assert_eq!(u64::MAX.to_i64(), None);
+        assert_eq!(u8::MAX.successor(), None);
+        assert_eq!(i8::MIN.predecessor(), None);
+        assert_eq!(BigInt::from(i64::MAX).successor().unwrap().to_i64(), None);
+    }
+
+    // Test that long digit strings parse as big integers and bad input fails as usual
+    #[test]
+    fn parse_digits_big() {
+        let digits = format!("1{}", "0".repeat(100));
+        assert_eq!(BigInt::parse_digits(&digits), Ok(BigInt::from(10).pow(100)));
+        assert_eq!(BigInt::parse_digits("-42"), Ok(BigInt::from(-42)));
+        assert_eq!(
+            BigInt::parse_digits("").unwrap_err().kind(),
+            &IntErrorKind::Empty
+        );
+        assert_eq!(
+            BigInt::parse_digits("12x").unwrap_err().kind(),
+            &IntErrorKind::InvalidDigit
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{integer::Integer, GuessResult};
+use num_bigint::BigUint;
+
+/// Enum to represent why a guess cannot tell the player anything new.
+///
+/// # Variants
+///
+/// - `Repeated`: The value was already guessed.
+/// - `AlreadyAbove { bound }`: Earlier feedback already showed the secret is above `bound`,
+///   and the guess is not.
+/// - `AlreadyBelow { bound }`: Earlier feedback already showed the secret is below `bound`,
+///   and the guess is not.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WastedGuess<T = u32> {
+    Repeated,
+    AlreadyAbove { bound: T },
+    AlreadyBelow { bound: T },
+}
+
+/// A struct tracking the values still consistent with the feedback given so far.
+///
+/// The `KnownInterval` struct is the player's knowledge about the secret number: it starts
+/// as the whole range of the game and shrinks with every "too small" and "too big" answer.
+/// It can tell whether a guess is wasted, which is what the game warns about, and it is
+/// equally useful to a solver choosing its next guess or a UI drawing the remaining range.
+/// It works with any integer type, including `BigInt`, and no bound ever overflows.
+///
+/// # Fields
+/// - `min`, `max`: The smallest and largest values the secret number can still be.
+/// - `guessed`: The values guessed so far, in the order they were recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownInterval<T = u32> {
+    min: T,
+    max: T,
+    guessed: Vec<T>,
+}
+
+impl<T: Integer> KnownInterval<T> {
+    /// Creates an interval covering the whole range `min..=max`.
+    pub fn new(min: T, max: T) -> KnownInterval<T> {
+        KnownInterval {
+            min,
+            max,
+            guessed: Vec::new(),
+        }
+    }
+
+    /// Narrows the interval with the feedback for a guess.
+    ///
+    /// Every "too small" answer raises the lower bound to just above that guess, and every
+    /// "too big" answer lowers the upper bound to just below it. A correct guess pins the
+    /// interval to that single value.
+    ///
+    /// # Arguments
+    /// * `value` - The value that was guessed.
+    /// * `result` - The feedback the guess got.
+    pub fn record(&mut self, value: T, result: GuessResult) {
+        match result {
+            GuessResult::TooSmall => {
+                let bound = value.successor().unwrap_or_else(|| value.clone());
+                if bound > self.min {
+                    self.min = bound;
+                }
+            }
+            GuessResult::TooBig => {
+                let bound = value.predecessor().unwrap_or_else(|| value.clone());
+                if bound < self.max {
+                    self.max = bound;
+                }
+            }
+            GuessResult::Correct => (self.min, self.max) = (value.clone(), value.clone()),
+        }
+        self.guessed.push(value);
+    }
+
+    /// Returns the smallest and largest values the secret number can still be.
+    pub fn bounds(&self) -> (T, T) {
+        (self.min.clone(), self.max.clone())
+    }
+
+    /// Checks whether `value` is still a possible secret number.
+    pub fn contains(&self, value: T) -> bool {
+        (&self.min..=&self.max).contains(&&value)
+    }
+
+    /// Returns how many values the secret number can still be.
+    ///
+    /// This is `0` only if the feedback contradicts itself. The count is a `BigUint`, since
+    /// it can be larger than any value of `T`, e.g. for the full range of `u128`.
+    pub fn remaining(&self) -> BigUint {
+        if self.min > self.max {
+            return BigUint::default();
+        }
+        T::distance(&self.min, &self.max) + 1u32
+    }
+
+    /// Returns the middle of the interval, the guess a binary search would make next.
+    pub fn midpoint(&self) -> T {
+        if self.min > self.max {
+            return self.min.clone();
+        }
+        T::middle(&self.min, &self.max)
+    }
+
+    /// Checks whether guessing `value` would be wasted.
+    ///
+    /// # Arguments
+    /// * `value` - The value the player is about to guess.
+    ///
+    /// # Returns
+    /// Returns an `Option`:
+    /// - `None` if the guess can still be the secret number.
+    /// - `Some(WastedGuess)` explaining why the answer is already known.
+    pub fn check(&self, value: T) -> Option<WastedGuess<T>> {
+        if self.guessed.contains(&value) {
+            return Some(WastedGuess::Repeated);
+        }
+        // A value outside the interval means a bound has moved, so it has a neighbour.
+        if value < self.min {
+            self.min
+                .predecessor()
+                .map(|bound| WastedGuess::AlreadyAbove { bound })
+        } else if value > self.max {
+            self.max
+                .successor()
+                .map(|bound| WastedGuess::AlreadyBelow { bound })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that feedback narrows the interval
+    #[test]
+    fn record_narrows() {
+        let mut interval = KnownInterval::new(1, 100);
+        assert_eq!(interval.remaining(), BigUint::from(100u32));
+        interval.record(50, GuessResult::TooSmall);
+        interval.record(75, GuessResult::TooBig);
+        assert_eq!(interval.bounds(), (51, 74));
+        assert_eq!(interval.remaining(), BigUint::from(24u32));
+        assert_eq!(interval.midpoint(), 62);
+        assert!(interval.contains(51));
+        assert!(!interval.contains(75));
+        interval.record(60, GuessResult::Correct);
+        assert_eq!(interval.bounds(), (60, 60));
+    }
+
+    // Test that wasted guesses are recognised
+    #[test]
+    fn check_wasted_guesses() {
+        let mut interval = KnownInterval::new(1, 100);
+        interval.record(62, GuessResult::TooSmall);
+        interval.record(80, GuessResult::TooBig);
+        assert_eq!(interval.check(70), None);
+        assert_eq!(interval.check(62), Some(WastedGuess::Repeated));
+        assert_eq!(
+            interval.check(40),
+            Some(WastedGuess::AlreadyAbove { bound: 62 })
+        );
+        assert_eq!(
+            interval.check(90),
+            Some(WastedGuess::AlreadyBelow { bound: 80 })
+        );
+    }
+
+    // Test the edges of the integer range do not overflow
+    #[test]
+    fn extreme_bounds() {
+        let mut interval = KnownInterval::new(0, u32::MAX);
+        assert_eq!(interval.remaining(), BigUint::from(u32::MAX) + 1u32);
+        assert_eq!(interval.midpoint(), u32::MAX / 2);
+        interval.record(u32::MAX - 1, GuessResult::TooSmall);
+        assert_eq!(interval.bounds(), (u32::MAX, u32::MAX));
+        assert_eq!(interval.check(u32::MAX), None);
+    }
+
+    // Test an interval over negative numbers
+    #[test]
+    fn signed_bounds() {
+        let mut interval = KnownInterval::new(-500i64, 500);
+        assert_eq!(interval.remaining(), BigUint::from(1001u32));
+        assert_eq!(interval.midpoint(), 0);
+        interval.record(0, GuessResult::TooBig);
+        interval.record(-250, GuessResult::TooSmall);
+        assert_eq!(interval.bounds(), (-249, -1));
+        assert_eq!(
+            interval.check(-300),
+            Some(WastedGuess::AlreadyAbove { bound: -250 })
+        );
+        assert_eq!(interval.remaining(), BigUint::from(249u32));
+
+        let full = KnownInterval::new(i128::MIN, i128::MAX);
+        assert_eq!(full.remaining(), BigUint::from(u128::MAX) + 1u32);
+    }
+}
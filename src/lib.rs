@@ -1,5 +1,47 @@
 use rand::Rng;
-use std::{cmp::Ordering, io, num::ParseIntError};
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt,
+    io::{self, BufRead, Write},
+    num::{IntErrorKind, ParseIntError},
+};
+
+pub mod batch;
+pub mod circle;
+pub mod classic;
+pub mod command;
+pub mod date;
+pub mod decimal;
+pub mod delay;
+pub mod dictionary;
+pub mod drift;
+pub mod expr;
+pub mod group;
+pub mod history;
+pub mod i18n;
+pub mod integer;
+pub mod interval;
+pub mod open;
+pub mod options;
+pub mod party;
+pub mod point;
+pub mod prompt;
+pub mod question;
+pub mod secrets;
+pub mod streak;
+pub mod theme;
+pub mod ui;
+pub mod words;
+
+use command::Command;
+use expr::ExprError;
+use integer::{Integer, Primitive};
+use num_bigint::BigInt;
+use rand::distributions::uniform::SampleUniform;
+use theme::Role;
+use ui::Ui;
+use words::WordsError;
 
 /// Trait for handling errors in a modular and consistent way.
 ///
@@ -13,10 +55,13 @@ use std::{cmp::Ordering, io, num::ParseIntError};
 ///
 /// # Methods
 ///
-/// ## `handle_error(&self)`
+/// ## `handle_error(&self, ui: &Ui, out: &mut dyn Write) -> io::Result<()>`
 ///
 /// A method that handles the error associated with the type implementing the trait.
-/// The implementation should define how the error should be processed or displayed.
+/// The implementation should define how the error should be processed or displayed,
+/// using the supplied `Ui` to translate and style anything it writes to `out`. Writing
+/// to a supplied sink rather than straight to stdout lets library users capture, log or
+/// redirect error messages as they see fit.
 ///
 /// # Use Case
 /// This trait is especially useful when we want to define a consistent way of handling errors
@@ -32,7 +77,61 @@ pub trait ErrorHandler {
     ///
     /// This method should define how errors of this type should be processed,
     /// logged, or displayed. The specific behavior is left up to the implementation.
-    fn handle_error(&self);
+    ///
+    /// # Arguments
+    /// * `ui` - The `Ui` used to translate and style any output.
+    /// * `out` - The sink the error message is written to, such as `io::stdout()` or a `Vec<u8>`.
+    ///
+    /// # Errors
+    /// Returns any error raised while writing to `out`.
+    fn handle_error(&self, ui: &Ui, out: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Enum to represent what a game mode expected when its input was invalid, as carried by
+/// `GuessError::Invalid`.
+///
+/// A game mode with its own kind of input adds a variant here, with its message in the
+/// catalogs, instead of a new `GuessError` variant.
+///
+/// # Variants
+///
+/// - `Word`: A word from the word list of word mode.
+/// - `Date`: A date in one of the accepted formats that exists in the calendar.
+/// - `Set`: A well-formed set of numbers in group testing.
+/// - `Question`: A question in the query language of twenty questions.
+/// - `Call`: A call of higher or lower in the higher-or-lower game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputKind {
+    Word,
+    Date,
+    Set,
+    Question,
+    Call,
+}
+
+impl InputKind {
+    /// Returns the catalog key of the error message for input that is not of this kind.
+    pub fn key(&self) -> &'static str {
+        match self {
+            InputKind::Word => "error-unknown-word",
+            InputKind::Date => "error-invalid-date",
+            InputKind::Set => "error-invalid-set",
+            InputKind::Question => "error-invalid-question",
+            InputKind::Call => "error-invalid-call",
+        }
+    }
+
+    /// Returns a short English description of this kind of input, which completes
+    /// "'input' is not ...".
+    pub fn description(&self) -> &'static str {
+        match self {
+            InputKind::Word => "in the word list",
+            InputKind::Date => "a valid date",
+            InputKind::Set => "a valid set",
+            InputKind::Question => "a valid question",
+            InputKind::Call => "a call of higher or lower",
+        }
+    }
 }
 
 /// Enum to represent possible errors when handling guesses.
@@ -43,61 +142,434 @@ pub trait ErrorHandler {
 /// input to out-of-range guesses. This enum is used to provide clear error reporting
 /// and handle different types of errors in a structured manner.
 ///
+/// The type parameter `T` is the type of the numbers being guessed, which only appears in
+/// `OutOfRange` and `BelowMinimum`. It defaults to `u32`, the type of the classic game.
+///
 /// # Variants
 ///
-/// ## `InvalidRange`
+/// ## `OutOfRange { value, min, max }`
 ///
 /// Represents an error that occurs when the user's guess is outside the valid range.
-/// The valid range for guesses is between 1 and 100, inclusive. This variant is returned
-/// when a guess is made that falls outside this range.
+/// It carries the rejected `value` together with the `min` and `max` of the active range
+/// (both inclusive), so messages can say exactly which bound was crossed.
 ///
-/// ## `ParseError(ParseIntError)`
+/// ## `BelowMinimum { value, min }`
 ///
-/// Represents an error that occurs when parsing the user's input into a valid `u32`.
-/// This variant contains the original `ParseIntError` returned when attempting to convert
-/// a non-numeric string into a number. This error may occur if the user enters a non-numeric
-/// value or improperly formatted input.
+/// Represents a guess below the smallest valid value in a game whose range has no maximum,
+/// such as the open-ended game. It carries the rejected `value` and the inclusive `min`.
 ///
-/// ## `InvalidInput`
+/// ## `Empty`
 ///
-/// Represents a general invalid input error. This variant is used for situations where
-/// the input doesn't conform to the expected format but is not necessarily a parsing error.
-/// It can be used for cases like empty input or special characters that aren't valid in a guess.
-#[derive(Debug, PartialEq)]
-pub enum GuessError {
-    InvalidRange,
-    ParseError(ParseIntError),
-    InvalidInput,
+/// Represents input that contains nothing but whitespace, such as pressing Enter
+/// without typing a guess.
+///
+/// ## `Negative { input }`
+///
+/// Represents a well-formed negative number while guessing an unsigned type, which can never
+/// hold it, so the number is rejected before any range check. The trimmed `input` is kept
+/// for the error message.
+///
+/// ## `Overflow { input }`
+///
+/// Represents a well-formed number that is too large, or too far below zero, to be stored as
+/// a guess at all.
+///
+/// ## `TooPrecise { input, places }`
+///
+/// Represents a decimal number with more decimal places than the game uses, such as `3.141`
+/// in a game with 2 places. The trimmed `input` and the number of `places` are kept for the
+/// error message.
+///
+/// ## `WrongDimensions { input, expected }`
+///
+/// Represents a guessed point that does not have one coordinate for each of the `expected`
+/// axes, such as `12` or `12,40,7` on a 2D grid. The trimmed `input` is kept for the error
+/// message.
+///
+/// ## `TooManyGuesses { input, limit }`
+///
+/// Represents a list of guesses in batch mode with more than the `limit` guesses allowed in
+/// one turn. The trimmed `input` is kept for the error message.
+///
+/// ## `Invalid { input, kind }`
+///
+/// Represents input that is not what a game mode expects, such as a word that is not in the
+/// word list, a day that does not exist such as `2023-02-29`, a set like `{3,,7}`, a question
+/// like `bigger than 40` or a call that is neither higher nor lower. The `kind` says what was
+/// expected, and the trimmed `input` is kept for the error message.
+///
+/// ## `AmbiguousNumber { input }`
+///
+/// Represents input made only of number words that do not form exactly one number, such as
+/// "five six" or "hundred hundred". The trimmed `input` is kept for the error message.
+///
+/// ## `NotANumber { input, source }`
+///
+/// Represents input that is not a number. This variant contains the trimmed `input` and the
+/// original `ParseIntError` returned when attempting to convert it into a number.
+///
+/// ## `InvalidExpression { input }`
+///
+/// Represents an arithmetic expression that is not well-formed, such as `3+` or `(4`.
+///
+/// ## `DivisionByZero { input }`
+///
+/// Represents an arithmetic expression that divides by zero, such as `10/(5-5)`.
+///
+/// ## `ExpressionOverflow { input }`
+///
+/// Represents an arithmetic expression with a step too large to calculate, such as `2^99`.
+///
+/// ## `NoPreviousGuess`
+///
+/// Represents an expression using `last` before any guess has been made.
+///
+/// ## `UnknownCommand { input }`
+///
+/// Represents input that starts with a colon, like a command, but names no known command.
+/// The trimmed `input` is kept for the error message.
+///
+/// ## `Io(io::Error)`
+///
+/// Represents a failure to read the user's input, for example because stdin was closed
+/// or could not be decoded. This variant contains the original `io::Error`.
+///
+/// ## `EndOfInput`
+///
+/// Represents reaching the end of the input, for example when stdin is closed or a piped
+/// file runs out. No more guesses can be read, so the game cannot continue.
+///
+/// # Traits
+///
+/// `GuessError` implements `Display` with a short English description and `std::error::Error`,
+/// whose `source()` returns the underlying `ParseIntError` or `io::Error`, so it can be used
+/// with `?` and any error-reporting library. Two `Io` errors compare equal when their
+/// `io::ErrorKind`s match, since `io::Error` itself cannot be compared.
+#[derive(Debug)]
+pub enum GuessError<T = u32> {
+    OutOfRange {
+        value: T,
+        min: T,
+        max: T,
+    },
+    BelowMinimum {
+        value: T,
+        min: T,
+    },
+    Empty,
+    Negative {
+        input: String,
+    },
+    Overflow {
+        input: String,
+    },
+    TooPrecise {
+        input: String,
+        places: u32,
+    },
+    WrongDimensions {
+        input: String,
+        expected: usize,
+    },
+    TooManyGuesses {
+        input: String,
+        limit: usize,
+    },
+    Invalid {
+        input: String,
+        kind: InputKind,
+    },
+    AmbiguousNumber {
+        input: String,
+    },
+    NotANumber {
+        input: String,
+        source: ParseIntError,
+    },
+    InvalidExpression {
+        input: String,
+    },
+    DivisionByZero {
+        input: String,
+    },
+    ExpressionOverflow {
+        input: String,
+    },
+    NoPreviousGuess,
+    UnknownCommand {
+        input: String,
+    },
+    Io(io::Error),
+    EndOfInput,
+}
+
+impl<T> GuessError<T> {
+    /// Returns `true` if the error means no more guesses can be read, so the game has to end.
+    ///
+    /// This is the case for `GuessError::EndOfInput` and `GuessError::Io`, the errors
+    /// `GameOutcome::from_error` ends the game for. Every other error is about a single bad
+    /// guess, and the player can simply try again.
+    pub fn is_fatal(&self) -> bool {
+        GameOutcome::from_error(self).is_some()
+    }
+
+    /// Converts the values carried by an `OutOfRange` or `BelowMinimum` error with `f`,
+    /// keeping every other error as it is.
+    ///
+    /// This lets a game change how the values are shown, e.g. with the decimal separator of
+    /// the player's language, before the error is reported.
+    pub fn map_value<U>(self, mut f: impl FnMut(T) -> U) -> GuessError<U> {
+        match self {
+            GuessError::OutOfRange { value, min, max } => GuessError::OutOfRange {
+                value: f(value),
+                min: f(min),
+                max: f(max),
+            },
+            GuessError::BelowMinimum { value, min } => GuessError::BelowMinimum {
+                value: f(value),
+                min: f(min),
+            },
+            GuessError::Empty => GuessError::Empty,
+            GuessError::Negative { input } => GuessError::Negative { input },
+            GuessError::Overflow { input } => GuessError::Overflow { input },
+            GuessError::TooPrecise { input, places } => GuessError::TooPrecise { input, places },
+            GuessError::WrongDimensions { input, expected } => {
+                GuessError::WrongDimensions { input, expected }
+            }
+            GuessError::TooManyGuesses { input, limit } => {
+                GuessError::TooManyGuesses { input, limit }
+            }
+            GuessError::Invalid { input, kind } => GuessError::Invalid { input, kind },
+            GuessError::AmbiguousNumber { input } => GuessError::AmbiguousNumber { input },
+            GuessError::NotANumber { input, source } => GuessError::NotANumber { input, source },
+            GuessError::InvalidExpression { input } => GuessError::InvalidExpression { input },
+            GuessError::DivisionByZero { input } => GuessError::DivisionByZero { input },
+            GuessError::ExpressionOverflow { input } => GuessError::ExpressionOverflow { input },
+            GuessError::NoPreviousGuess => GuessError::NoPreviousGuess,
+            GuessError::UnknownCommand { input } => GuessError::UnknownCommand { input },
+            GuessError::Io(e) => GuessError::Io(e),
+            GuessError::EndOfInput => GuessError::EndOfInput,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for GuessError<T> {
+    fn eq(&self, other: &Self) -> bool {
+        use GuessError::*;
+        match (self, other) {
+            (
+                OutOfRange { value, min, max },
+                OutOfRange {
+                    value: v,
+                    min: lo,
+                    max: hi,
+                },
+            ) => (value, min, max) == (v, lo, hi),
+            (BelowMinimum { value, min }, BelowMinimum { value: v, min: lo }) => {
+                (value, min) == (v, lo)
+            }
+            (Empty, Empty) => true,
+            (Negative { input: a }, Negative { input: b }) => a == b,
+            (Overflow { input: a }, Overflow { input: b }) => a == b,
+            (
+                TooPrecise { input, places },
+                TooPrecise {
+                    input: i,
+                    places: p,
+                },
+            ) => (input, places) == (i, p),
+            (
+                WrongDimensions { input, expected },
+                WrongDimensions {
+                    input: i,
+                    expected: e,
+                },
+            ) => (input, expected) == (i, e),
+            (TooManyGuesses { input, limit }, TooManyGuesses { input: i, limit: l }) => {
+                (input, limit) == (i, l)
+            }
+            (Invalid { input, kind }, Invalid { input: i, kind: k }) => (input, kind) == (i, k),
+            (AmbiguousNumber { input: a }, AmbiguousNumber { input: b }) => a == b,
+            (NotANumber { input: a, .. }, NotANumber { input: b, .. }) => a == b,
+            (InvalidExpression { input: a }, InvalidExpression { input: b }) => a == b,
+            (DivisionByZero { input: a }, DivisionByZero { input: b }) => a == b,
+            (ExpressionOverflow { input: a }, ExpressionOverflow { input: b }) => a == b,
+            (NoPreviousGuess, NoPreviousGuess) => true,
+            (UnknownCommand { input: a }, UnknownCommand { input: b }) => a == b,
+            (Io(a), Io(b)) => a.kind() == b.kind(),
+            (EndOfInput, EndOfInput) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: fmt::Display + PartialOrd> fmt::Display for GuessError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::OutOfRange { value, min, .. } if value < min => {
+                write!(f, "{} is below the minimum of {}", value, min)
+            }
+            GuessError::OutOfRange { value, max, .. } => {
+                write!(f, "{} is above the maximum of {}", value, max)
+            }
+            GuessError::BelowMinimum { value, min } => {
+                write!(f, "{} is below the minimum of {}", value, min)
+            }
+            GuessError::Empty => write!(f, "no guess was entered"),
+            GuessError::Negative { input } => write!(f, "{} is negative", input),
+            GuessError::Overflow { input } => write!(f, "{} is too large to be a guess", input),
+            GuessError::TooPrecise { input, places } => {
+                write!(f, "{} has more than {} decimal places", input, places)
+            }
+            GuessError::WrongDimensions { input, expected } => {
+                write!(f, "'{}' does not have {} coordinates", input, expected)
+            }
+            GuessError::TooManyGuesses { input, limit } => {
+                write!(f, "'{}' has more than {} guesses", input, limit)
+            }
+            GuessError::Invalid { input, kind } => {
+                write!(f, "'{}' is not {}", input, kind.description())
+            }
+            GuessError::AmbiguousNumber { input } => {
+                write!(f, "'{}' does not form exactly one number", input)
+            }
+            GuessError::NotANumber { input, .. } => write!(f, "'{}' is not a number", input),
+            GuessError::InvalidExpression { input } => {
+                write!(f, "'{}' is not a valid expression", input)
+            }
+            GuessError::DivisionByZero { input } => write!(f, "'{}' divides by zero", input),
+            GuessError::ExpressionOverflow { input } => {
+                write!(f, "'{}' is too large to calculate", input)
+            }
+            GuessError::NoPreviousGuess => write!(f, "there is no previous guess yet"),
+            GuessError::UnknownCommand { input } => write!(f, "unknown command '{}'", input),
+            GuessError::Io(e) => write!(f, "failed to read input: {}", e),
+            GuessError::EndOfInput => write!(f, "reached the end of the input"),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display + PartialOrd> Error for GuessError<T> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GuessError::NotANumber { source, .. } => Some(source),
+            GuessError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<io::Error> for GuessError<T> {
+    fn from(e: io::Error) -> Self {
+        GuessError::Io(e)
+    }
 }
 
 /// Handles the display of error messages based on the provided `GuessError`.
 ///
 /// This method is an implementation of the `ErrorHandler` trait for the `GuessError` enum.
-/// It prints an appropriate, user-friendly error message to the console depending on which variant
+/// It writes an appropriate, user-friendly error message to `out` depending on which variant
 /// of `GuessError` was encountered. The method provides clear feedback to the user, helping them
 /// understand the nature of the error and how to resolve it.
 ///
 /// # Arguments
 /// * `self` - The `GuessError` variant that occurred. This variant determines which error message is printed.
+/// * `ui` - The `Ui` used to translate the message into the player's language and style it
+///   in the error color.
+/// * `out` - The sink the message is written to.
 ///
 /// # Behavior
-/// - For `GuessError::InvalidRange`, it prints an error message indicating that the guess is outside
-///   the valid range (1 to 100).
-/// - For `GuessError::ParseError`, it indicates that the input could not be parsed into a valid number.
-/// - For `GuessError::InvalidInput`, it prints a more general error message, asking the user to try again.
-impl ErrorHandler for GuessError {
-    fn handle_error(&self) {
-        match self {
-            GuessError::InvalidRange => {
-                println!("Error: The number must be between 1 and 100.");
+/// - For `GuessError::OutOfRange`, it names the rejected value and the bound it crossed,
+///   e.g. "150 is above the maximum of 100".
+/// - For `GuessError::BelowMinimum`, it names the rejected value and the minimum.
+/// - For `GuessError::Empty`, it asks the user to type a number.
+/// - For `GuessError::Negative` and `GuessError::Overflow`, it repeats the input and explains
+///   why it cannot be a guess.
+/// - For `GuessError::TooPrecise`, it repeats the input and says how many decimal places
+///   are allowed.
+/// - For `GuessError::WrongDimensions`, it repeats the input and says how many coordinates
+///   a point has.
+/// - For `GuessError::TooManyGuesses`, it repeats the input and says how many guesses a turn
+///   may have.
+/// - For `GuessError::Invalid`, it repeats the input with the message of its `InputKind`,
+///   which says what the game mode expects, e.g. how to write a date.
+/// - For `GuessError::AmbiguousNumber`, it repeats the input and shows how to write a number.
+/// - For `GuessError::NotANumber`, it repeats the input that could not be parsed.
+/// - For the expression errors, it repeats the expression and explains what went wrong.
+/// - For `GuessError::UnknownCommand`, it repeats the command and points to `:help`.
+/// - For `GuessError::Io`, it reports that the input could not be read, including the reason.
+/// - For `GuessError::EndOfInput`, it reports that there is no more input.
+impl<T: fmt::Display + PartialOrd> ErrorHandler for GuessError<T> {
+    fn handle_error(&self, ui: &Ui, out: &mut dyn Write) -> io::Result<()> {
+        let message = match self {
+            GuessError::OutOfRange { value, min, .. } if value < min => ui.format(
+                Role::Error,
+                "error-below-min",
+                &[("value", value), ("min", min)],
+            ),
+            GuessError::OutOfRange { value, max, .. } => ui.format(
+                Role::Error,
+                "error-above-max",
+                &[("value", value), ("max", max)],
+            ),
+            GuessError::BelowMinimum { value, min } => ui.format(
+                Role::Error,
+                "error-below-min",
+                &[("value", value), ("min", min)],
+            ),
+            GuessError::Empty => ui.text(Role::Error, "error-empty"),
+            GuessError::Negative { input } => {
+                ui.format(Role::Error, "error-negative", &[("input", input)])
             }
-            GuessError::ParseError(_) => {
-                println!("Error: Please enter a valid number.");
+            GuessError::Overflow { input } => {
+                ui.format(Role::Error, "error-overflow", &[("input", input)])
             }
-            GuessError::InvalidInput => {
-                println!("Error: Invalid input, please try again.");
+            GuessError::TooPrecise { input, places } => ui.plural(
+                Role::Error,
+                "error-too-precise",
+                u64::from(*places),
+                &[("input", input)],
+            ),
+            GuessError::WrongDimensions { input, expected } => ui.plural(
+                Role::Error,
+                "error-wrong-dimensions",
+                *expected as u64,
+                &[("input", input)],
+            ),
+            GuessError::TooManyGuesses { input, limit } => ui.plural(
+                Role::Error,
+                "error-too-many-guesses",
+                *limit as u64,
+                &[("input", input)],
+            ),
+            GuessError::Invalid { input, kind } => {
+                ui.format(Role::Error, kind.key(), &[("input", input)])
             }
-        }
+            GuessError::AmbiguousNumber { input } => {
+                ui.format(Role::Error, "error-ambiguous-number", &[("input", input)])
+            }
+            GuessError::NotANumber { input, .. } => {
+                ui.format(Role::Error, "error-not-a-number", &[("input", input)])
+            }
+            GuessError::InvalidExpression { input } => {
+                ui.format(Role::Error, "error-invalid-expression", &[("input", input)])
+            }
+            GuessError::DivisionByZero { input } => {
+                ui.format(Role::Error, "error-division-by-zero", &[("input", input)])
+            }
+            GuessError::ExpressionOverflow { input } => ui.format(
+                Role::Error,
+                "error-expression-overflow",
+                &[("input", input)],
+            ),
+            GuessError::NoPreviousGuess => ui.text(Role::Error, "error-no-previous-guess"),
+            GuessError::UnknownCommand { input } => {
+                ui.format(Role::Error, "error-unknown-command", &[("input", input)])
+            }
+            GuessError::Io(e) => ui.format(Role::Error, "error-io", &[("reason", e)]),
+            GuessError::EndOfInput => ui.text(Role::Error, "error-end-of-input"),
+        };
+        writeln!(out, "{}", message)
     }
 }
 
@@ -113,9 +585,16 @@ impl ErrorHandler for GuessError {
 /// in a variety of contexts, such as in a game or command-line application, ensuring that
 /// input is correctly parsed or appropriate error messages are returned.
 ///
+/// # Associated Type
+///
+/// ## `Value`
+///
+/// The type of the values being guessed, which is what a `GuessError::OutOfRange` reports.
+/// For a `Guess<T>` this is `T`.
+///
 /// # Associated Function
 ///
-/// ## `parse_input(input: &str) -> Result<Self, GuessError>`
+/// ## `parse_input(input: &str) -> Result<Self, GuessError<Self::Value>>`
 ///
 /// Attempts to parse the provided string into the implementing type. If the input is valid,
 /// it returns `Ok(self)`. Otherwise, it returns a `GuessError` indicating what went wrong.
@@ -123,9 +602,12 @@ impl ErrorHandler for GuessError {
 /// - **`input`**: A string slice (`&str`) containing the user input to be parsed.
 /// - **Returns**: A `Result`:
 ///   - `Ok(Self)` if the parsing was successful.
-///   - `Err(GuessError)` if the parsing failed. This error could be a `ParseError`, `InvalidRange`, or
-///      other types of errors depending on the implementation.
+///   - `Err(GuessError)` if the parsing failed. This error could be a `NotANumber`, `OutOfRange`, or
+///     other types of errors depending on the implementation.
 pub trait Parsable {
+    /// The type of the values being guessed.
+    type Value;
+
     /// Parses a string input into a valid value of the implementing type.
     ///
     /// # Arguments
@@ -134,8 +616,8 @@ pub trait Parsable {
     /// # Returns
     /// - `Ok(Self)` if the parsing is successful.
     /// - `Err(GuessError)` if the input is invalid, where the error could be a parsing error or some
-    ///    other validation failure.
-    fn parse_input(input: &str) -> Result<Self, GuessError>
+    ///   other validation failure.
+    fn parse_input(input: &str) -> Result<Self, GuessError<Self::Value>>
     where
         Self: Sized;
 }
@@ -144,64 +626,166 @@ pub trait Parsable {
 ///
 /// The `Guessable` trait provides a common interface for objects that can be "guessed" in the
 /// context of a guessing game. Types that implement this trait must define how they compare
-/// themselves to another object of the same type. This trait is useful for defining objects like
-/// `Guess` or `secret_number` that need to be compared during gameplay, such as determining whether
-/// a guess is too high, too low, or correct.
+/// themselves to another object of the same type, and what that comparison tells the player.
+/// This trait is useful for defining objects like `Guess` or `secret_number` that need to be
+/// compared during gameplay, such as determining whether a guess is too high, too low, or
+/// correct.
 ///
 /// Implementing this trait allows various types to participate in the guessing game by providing
 /// a standardized comparison operation, which is essential for determining the outcome of a guess.
 ///
+/// # Associated Type
+///
+/// ## `Feedback`
+///
+/// What one comparison tells the player. Secrets that are ordered, such as numbers, words or
+/// dates, use `GuessResult`; a secret point uses `PointComparison`, which has a result for
+/// each axis and the distance to the secret.
+///
 /// # Associated Function
 ///
-/// ## `compare(&self, other: &Self) -> Ordering`
+/// ## `compare(&self, other: &Self) -> Self::Feedback`
 ///
-/// Compares two objects of the same type and returns an `Ordering` that indicates whether the first
-/// object is less than, greater than, or equal to the second object. The exact comparison logic depends
-/// on the implementing type.
+/// Compares two objects of the same type and returns the feedback for the first object when the
+/// second one is the secret. The exact comparison logic depends on the implementing type.
 pub trait Guessable {
+    /// What comparing an object with the secret tells the player.
+    type Feedback;
+
     /// Compares two objects of the same type.
     ///
-    /// This method compares the current object (`self`) with another object of the same type (`other`)
-    /// and returns an `Ordering`. The result will be:
-    /// - `Ordering::Less` if the current object is considered "smaller" than the other.
-    /// - `Ordering::Greater` if the current object is considered "larger" than the other.
-    /// - `Ordering::Equal` if the two objects are considered equal.
+    /// This method compares the current object (`self`) with another object of the same type
+    /// (`other`) and returns the feedback for `self` as a guess of `other`. For a
+    /// `GuessResult`, the result will be:
+    /// - `GuessResult::TooSmall` if the current object is considered "smaller" than the other.
+    /// - `GuessResult::TooBig` if the current object is considered "larger" than the other.
+    /// - `GuessResult::Correct` if the two objects are considered equal.
     ///
     /// The exact comparison logic is determined by the implementing type.
-    fn compare(&self, other: &Self) -> std::cmp::Ordering;
+    fn compare(&self, other: &Self) -> Self::Feedback;
+}
+
+/// A trait for yes-or-no questions about a secret.
+///
+/// The `Query` trait is the counterpart of `Guessable` for games where the player does not
+/// compare a guess with the secret, but asks whether the secret has some property, such as
+/// being in a set of numbers. `handle_query` turns the answer into an `Answer`.
+///
+/// # Associated Function
+///
+/// ## `matches(&self, secret: &T) -> bool`
+///
+/// Returns `true` if the secret has the property the query asks about.
+pub trait Query<T> {
+    /// Checks whether `secret` has the property the query asks about.
+    fn matches(&self, secret: &T) -> bool;
 }
 
 /// A struct representing a guess made by the user.
 ///
-/// The `Guess` struct stores a single guess made by the user in a guessing game. It contains a `u32`
-/// value that represents the guessed number. The guess is typically within a predefined range (usually
-/// between 1 and 100), but this range can be customized. The struct provides methods to create a new guess,
-/// retrieve its value, and check if the guess is valid.
+/// The `Guess` struct stores a single guess made by the user in a guessing game. It contains a
+/// value of any integer type `T` (see the `integer` module), which defaults to `u32`.
+/// The guess is checked against a range when it is created: the classic range of 1 to 100 with
+/// `Guess::new`, or any other range with `Guess::in_range`, such as -500 to 500 with `i32`,
+/// 0 to 10^15 with `u64`, or 1 to 10^100 with `BigInt`. The struct provides methods to create a
+/// new guess, retrieve its value, and parse it from user input.
 ///
 /// The `Guess` struct can be used in any context that involves guessing numbers, such as in games or quizzes
 /// where players are asked to make a numeric guess and the system checks if the guess is correct.
 ///
 /// # Fields
 ///
-/// - `value`: The numeric value of the user's guess, stored as a `u32`.
-#[derive(Debug, PartialEq)]
-pub struct Guess {
-    value: u32,
+/// - `value`: The numeric value of the user's guess, stored as a `T`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess<T = u32> {
+    value: T,
 }
 
-impl Guess {
+impl<T: Primitive> Guess<T> {
+    /// The smallest valid guess of the classic game.
+    pub const MIN: T = T::DEFAULT_MIN;
+
+    /// The largest valid guess of the classic game.
+    pub const MAX: T = T::DEFAULT_MAX;
+
     /// Creates a new guess with the given value.
     ///
     /// # Arguments
-    /// * `value` - A `u32` representing the user's guess.
+    /// * `value` - A `T` representing the user's guess.
+    ///
+    /// # Returns
+    /// Returns a `Result`:
+    /// - `Ok(Guess)` if the guess is within the valid range (`Guess::MIN` to `Guess::MAX`).
+    /// - `Err(GuessError::OutOfRange)` if the guess is outside the valid range. The error
+    ///   carries the rejected value and the bounds of the range.
+    pub fn new(value: T) -> Result<Guess<T>, GuessError<T>> {
+        Guess::in_range(value, Guess::MIN, Guess::MAX)
+    }
+
+    /// Parses user input into a guess, with `last` standing for the previous guess.
+    ///
+    /// This does everything `Parsable::parse_input` does, and additionally lets arithmetic
+    /// expressions such as `(37+62)/2` or `last+10` refer to the previous guess through the
+    /// word `last` (see the `expr` module).
+    ///
+    /// # Arguments
+    /// * `input` - The user input to parse.
+    /// * `last` - The value of the previous guess, or `None` if this is the first guess.
+    ///
+    /// # Returns
+    /// Returns the same results as `Parsable for Guess`, plus
+    /// `Err(GuessError::NoPreviousGuess)` if `last` is used while `last` is `None`.
+    pub fn parse_with_last(input: &str, last: Option<T>) -> Result<Guess<T>, GuessError<T>> {
+        Guess::parse_in_range(input, Guess::MIN, Guess::MAX, last)
+    }
+}
+
+impl Guess<BigInt> {
+    /// The number of digits of the largest secret number in impossible mode.
+    pub const IMPOSSIBLE_DIGITS: u32 = 100;
+
+    /// Returns the range of impossible mode, 1 to 10^100.
+    ///
+    /// Finding a number in this range takes a binary search about 333 guesses.
+    pub fn impossible_range() -> (BigInt, BigInt) {
+        (
+            BigInt::from(1),
+            BigInt::from(10).pow(Guess::IMPOSSIBLE_DIGITS),
+        )
+    }
+}
+
+impl<T: Integer> Guess<T> {
+    /// Creates a new guess that must lie in the range `min..=max`.
+    ///
+    /// # Arguments
+    /// * `value` - The user's guess.
+    /// * `min`, `max` - The smallest and largest valid guesses.
+    ///
+    /// # Returns
+    /// Returns a `Result`:
+    /// - `Ok(Guess)` if the guess is within `min..=max`.
+    /// - `Err(GuessError::OutOfRange)` carrying the rejected value and the bounds otherwise.
+    pub fn in_range(value: T, min: T, max: T) -> Result<Guess<T>, GuessError<T>> {
+        if !(&min..=&max).contains(&&value) {
+            return Err(GuessError::OutOfRange { value, min, max });
+        }
+        Ok(Guess { value })
+    }
+
+    /// Creates a new guess that must be at least `min`, with no maximum.
+    ///
+    /// # Arguments
+    /// * `value` - The user's guess.
+    /// * `min` - The smallest valid guess.
     ///
     /// # Returns
     /// Returns a `Result`:
-    /// - `Ok(Guess)` if the guess is within the valid range (1 to 100).
-    /// - `Err(GuessError::InvalidRange)` if the guess is outside the valid range.
-    pub fn new(value: u32) -> Result<Guess, GuessError> {
-        if value < 1 || value > 100 {
-            return Err(GuessError::InvalidRange);
+    /// - `Ok(Guess)` if the guess is at least `min`.
+    /// - `Err(GuessError::BelowMinimum)` carrying the rejected value and `min` otherwise.
+    pub fn at_least(value: T, min: T) -> Result<Guess<T>, GuessError<T>> {
+        if value < min {
+            return Err(GuessError::BelowMinimum { value, min });
         }
         Ok(Guess { value })
     }
@@ -209,63 +793,209 @@ impl Guess {
     /// Returns the value of the guess.
     ///
     /// # Returns
-    /// Returns the `u32` value of the guess.
-    pub fn value(&self) -> u32 {
-        self.value
+    /// Returns the `T` value of the guess.
+    pub fn value(&self) -> T {
+        self.value.clone()
+    }
+
+    /// Parses user input into a guess that must lie in the range `min..=max`.
+    ///
+    /// This is `Guess::parse_with_last` for games played over any range. Numbers typed in
+    /// digits can be as long as `T` allows. Expressions are evaluated as `i64`s, so using
+    /// `last` while the previous guess does not fit in one gives
+    /// `GuessError::ExpressionOverflow`.
+    ///
+    /// # Arguments
+    /// * `input` - The user input to parse.
+    /// * `min`, `max` - The smallest and largest valid guesses.
+    /// * `last` - The value of the previous guess, or `None` if this is the first guess.
+    pub fn parse_in_range(
+        input: &str,
+        min: T,
+        max: T,
+        last: Option<T>,
+    ) -> Result<Guess<T>, GuessError<T>> {
+        Guess::parse_checked(input, last, |value| {
+            Guess::in_range(value, min.clone(), max.clone())
+        })
+    }
+
+    /// Parses user input into a guess that must be at least `min`, with no maximum.
+    ///
+    /// This is `Guess::parse_in_range` for games whose range is open-ended, and it accepts the
+    /// same numbers, number words and expressions.
+    ///
+    /// # Arguments
+    /// * `input` - The user input to parse.
+    /// * `min` - The smallest valid guess.
+    /// * `last` - The value of the previous guess, or `None` if this is the first guess.
+    pub fn parse_at_least(input: &str, min: T, last: Option<T>) -> Result<Guess<T>, GuessError<T>> {
+        Guess::parse_checked(input, last, |value| Guess::at_least(value, min.clone()))
+    }
+
+    /// Parses user input into a number and turns it into a guess with `check`, which rejects
+    /// the numbers outside the range of the game.
+    fn parse_checked(
+        input: &str,
+        last: Option<T>,
+        check: impl Fn(T) -> Result<Guess<T>, GuessError<T>>,
+    ) -> Result<Guess<T>, GuessError<T>> {
+        let input = input.trim();
+        let source = match T::parse_digits(input) {
+            Ok(guess) => return check(guess),
+            Err(source) => source,
+        };
+        let input_string = input.to_string();
+        match source.kind() {
+            IntErrorKind::Empty => Err(GuessError::Empty),
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Err(GuessError::Overflow {
+                input: input_string,
+            }),
+            _ if is_negative_number(input) => Err(GuessError::Negative {
+                input: input_string,
+            }),
+            _ => match words::parse_words_any(input) {
+                Ok(guess) => match T::from_u32(guess) {
+                    Some(guess) => check(guess),
+                    None => Err(GuessError::Overflow {
+                        input: input_string,
+                    }),
+                },
+                Err(WordsError::NotWords) if expr::looks_like_expression(input) => {
+                    Guess::from_expression(input, last, check)
+                }
+                Err(WordsError::Ambiguous) => Err(GuessError::AmbiguousNumber {
+                    input: input_string,
+                }),
+                Err(WordsError::NotWords) => Err(GuessError::NotANumber {
+                    input: input_string,
+                    source,
+                }),
+            },
+        }
+    }
+
+    /// Evaluates an arithmetic expression and turns its value into a guess.
+    ///
+    /// The value goes through the same checks as a typed number: a negative value gives
+    /// `GuessError::Negative` when `T` is unsigned, a value that does not fit in a `T` gives
+    /// `GuessError::Overflow`, and `check` checks the range.
+    fn from_expression(
+        input: &str,
+        last: Option<T>,
+        check: impl Fn(T) -> Result<Guess<T>, GuessError<T>>,
+    ) -> Result<Guess<T>, GuessError<T>> {
+        let input_string = input.to_string();
+        let last_i64 = last.as_ref().and_then(T::to_i64);
+        let value = expr::evaluate(input, last_i64).map_err(|e| match e {
+            ExprError::Syntax => GuessError::InvalidExpression {
+                input: input_string.clone(),
+            },
+            ExprError::DivisionByZero => GuessError::DivisionByZero {
+                input: input_string.clone(),
+            },
+            ExprError::Overflow => GuessError::ExpressionOverflow {
+                input: input_string.clone(),
+            },
+            // There was a previous guess, but it is too large to calculate with.
+            ExprError::NoPreviousGuess if last.is_some() => GuessError::ExpressionOverflow {
+                input: input_string.clone(),
+            },
+            ExprError::NoPreviousGuess => GuessError::NoPreviousGuess,
+        })?;
+        match T::from_i64(value) {
+            Some(value) => check(value),
+            None if value < 0 && !T::SIGNED => Err(GuessError::Negative {
+                input: input_string,
+            }),
+            None => Err(GuessError::Overflow {
+                input: input_string,
+            }),
+        }
     }
 }
 
 /// Trait implementation for `Guess` to make it "guessable".
 ///
 /// This trait allows the `Guess` struct to be compared to another `Guess` object
-/// using the `compare` method, which returns an `Ordering` based on the value of the guess.
+/// using the `compare` method, which returns a `GuessResult` based on the value of the guess.
 ///
 /// The `compare` method compares the value of the current `Guess` (`self`) to the value of
-/// another `Guess` (`other`). It returns an `Ordering` (`Less`, `Greater`, or `Equal`) depending
-/// on whether the current guess is smaller, larger, or equal to the other guess.
-impl Guessable for Guess {
+/// another `Guess` (`other`). It returns a `GuessResult` (`TooSmall`, `TooBig`, or `Correct`)
+/// depending on whether the current guess is smaller, larger, or equal to the other guess.
+impl<T: Integer> Guessable for Guess<T> {
+    type Feedback = GuessResult;
+
     /// Compares the value of the current guess (`self`) to another guess (`other`).
     ///
     /// # Arguments
     /// * `other` - A reference to another `Guess` instance to compare with.
     ///
     /// # Returns
-    /// Returns an `Ordering`:
-    /// - `Ordering::Less` if `self.value` is less than `other.value`.
-    /// - `Ordering::Greater` if `self.value` is greater than `other.value`.
-    /// - `Ordering::Equal` if both values are equal.
-    fn compare(&self, other: &Self) -> Ordering {
-        self.value.cmp(&other.value)
+    /// Returns a `GuessResult`:
+    /// - `GuessResult::TooSmall` if `self.value` is less than `other.value`.
+    /// - `GuessResult::TooBig` if `self.value` is greater than `other.value`.
+    /// - `GuessResult::Correct` if both values are equal.
+    fn compare(&self, other: &Self) -> GuessResult {
+        self.value.cmp(&other.value).into()
     }
 }
 
 /// Trait implementation for `Guess` to make it parsable from a string input.
 ///
 /// This trait allows a `Guess` object to be created from a string input.
-/// The input is first trimmed of any whitespace, then parsed as a `u32`
+/// The input is first trimmed of any whitespace, then parsed as a `T`
 /// value. If the parsing is successful, the `Guess::new` method is used
-/// to create a new `Guess` object. If any errors occur (e.g., the input
-/// is not a valid number or it's out of the valid range for guesses),
-/// the error is mapped to a `GuessError::ParseError`.
+/// to create a new `Guess` object. If the input is not made of digits, it is
+/// read as a number written in words in any supported language, such as
+/// "forty two", "cuarenta y dos" or "quarante-deux" (see the `words` module),
+/// or else evaluated as an arithmetic expression such as `(37+62)/2` or `2^6`
+/// (see the `expr` module). The parsing itself is done by `Guess::parse_in_range`.
+/// If parsing fails, the reason is worked out so the player gets a precise error.
 ///
 /// # Arguments
 /// * `input` - A string slice (`&str`) representing the input to be parsed into a `Guess`.
 ///
 /// # Returns
-/// Returns a `Result<Guess, GuessError>`:
+/// Returns a `Result<Guess<T>, GuessError<T>>`:
 /// - `Ok(Guess)` if the input is valid and within the allowed range (1 to 100).
-/// - `Err(GuessError::ParseError)` if the input cannot be parsed into a `u32`.
-/// - `Err(GuessError::InvalidRange)` if the parsed `u32` is outside the valid range.
-impl Parsable for Guess {
-    fn parse_input(input: &str) -> Result<Guess, GuessError> {
-        let guess = input
-            .trim()
-            .parse::<u32>()
-            .map_err(|e| GuessError::ParseError(e))?;
-        Guess::new(guess)
+/// - `Err(GuessError::Empty)` if the input is blank.
+/// - `Err(GuessError::Negative)` if the input is a negative number and `T` is unsigned.
+/// - `Err(GuessError::Overflow)` if the input is a number that does not fit in a `T`.
+/// - `Err(GuessError::AmbiguousNumber)` if the input is number words that don't form one number.
+/// - `Err(GuessError::InvalidExpression)`, `Err(GuessError::DivisionByZero)` or
+///   `Err(GuessError::ExpressionOverflow)` if an arithmetic expression cannot be evaluated.
+/// - `Err(GuessError::NotANumber)` if the input cannot be parsed into a number at all.
+/// - `Err(GuessError::OutOfRange)` if the parsed number is outside the valid range.
+impl<T: Primitive> Parsable for Guess<T> {
+    type Value = T;
+
+    fn parse_input(input: &str) -> Result<Guess<T>, GuessError<T>> {
+        Guess::parse_with_last(input, None)
     }
 }
 
+/// Trait implementation for big guesses to make them parsable from a string input.
+///
+/// This works like `Parsable for Guess<T>`, except that the range is the one of impossible
+/// mode, 1 to 10^100 (see `Guess::impossible_range`), and numbers can be typed with as many
+/// digits as that takes.
+impl Parsable for Guess<BigInt> {
+    type Value = BigInt;
+
+    fn parse_input(input: &str) -> Result<Guess<BigInt>, GuessError<BigInt>> {
+        let (min, max) = Guess::impossible_range();
+        Guess::parse_in_range(input, min, max, None)
+    }
+}
+
+/// Checks whether `input` is a minus sign followed by one or more digits.
+fn is_negative_number(input: &str) -> bool {
+    input
+        .strip_prefix('-')
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
 /// Trait for types that can be incremented.
 ///
 /// The `Incrementable` trait defines a contract for types that represent a counter or a
@@ -332,6 +1062,12 @@ impl GuessCount {
     }
 }
 
+impl Default for GuessCount {
+    fn default() -> Self {
+        GuessCount::new()
+    }
+}
+
 /// Trait implementation for `GuessCount` to make it incrementable.
 ///
 /// This trait allows the `GuessCount` struct to increment its `count` field by 1
@@ -354,12 +1090,15 @@ impl Incrementable for GuessCount {
 /// random number generator. The number generated is within the bounds specified
 /// by the `start` and `end` parameters, including both `start` and `end`.
 ///
+/// The function is generic over any type `rand` can sample uniformly, which includes every
+/// primitive integer type, so secrets can be drawn from ranges such as -500 to 500 or 0 to 10^15.
+///
 /// # Arguments
-/// * `start` - The lower bound of the range (inclusive), as a `u32`. This is the smallest value that can be returned.
-/// * `end` - The upper bound of the range (inclusive), as a `u32`. This is the largest value that can be returned.
+/// * `start` - The lower bound of the range (inclusive). This is the smallest value that can be returned.
+/// * `end` - The upper bound of the range (inclusive). This is the largest value that can be returned.
 ///
 /// # Returns
-/// Returns a `u32` value representing the random number generated within the range `[start, end]`.
+/// Returns a value representing the random number generated within the range `[start, end]`.
 ///
 /// # Notes
 /// - The `start` value must be less than or equal to the `end` value.
@@ -367,37 +1106,185 @@ impl Incrementable for GuessCount {
 ///
 /// # Panics
 /// This function will panic if `start` is greater than `end`, as the range is invalid.
-pub fn get_secret_number(start: u32, end: u32) -> u32 {
+pub fn get_secret_number<T: SampleUniform + PartialOrd>(start: T, end: T) -> T {
     rand::thread_rng().gen_range(start..=end)
 }
 
-/// Prompts the user for a guess and returns a `Result` containing the `Guess` object or an error.
+/// Enum to represent one line of input from the player.
+///
+/// # Variants
+///
+/// - `Guess(Guess)`: The player made a guess.
+/// - `Command(Command)`: The player typed a command such as `:hint` (see the `command` module).
+#[derive(Debug, PartialEq)]
+pub enum PlayerInput<T = u32> {
+    Guess(Guess<T>),
+    Command(Command),
+}
+
+/// Prompts the user for a guess and returns a `Result` containing the `PlayerInput` or an error.
 ///
 /// This function prompts the user to input a guess via the console, reads the input as a string,
-/// and attempts to parse it into a valid `Guess`. If the input is valid and within the allowed range,
-/// it returns an `Ok(Guess)`. If the input is invalid or outside the valid range, it returns
-/// an appropriate error (`ParseError` or `InvalidRange`).
+/// and attempts to parse it into a command or a valid `Guess`. If the input is valid and within the
+/// allowed range, it returns an `Ok(PlayerInput)`. If the input is invalid or outside the valid range,
+/// it returns an appropriate error such as `NotANumber`, `OutOfRange` or `UnknownCommand`.
+///
+/// # Arguments
+/// * `ui` - The `Ui` used to translate and style the prompt.
+/// * `last` - The value of the previous guess, which the input can refer to as `last`.
 ///
 /// # Returns
-/// Returns a `Result<Guess, GuessError>`:
-/// - `Ok(Guess)` if the user input is valid and within the range of 1 to 100.
-/// - `Err(GuessError::ParseError)` if the input cannot be parsed as a valid `u32`.
-/// - `Err(GuessError::InvalidRange)` if the parsed guess is outside the valid range (1 to 100).
+/// Returns a `Result<PlayerInput, GuessError>`:
+/// - `Ok(PlayerInput::Command)` if the user typed a known command.
+/// - `Ok(PlayerInput::Guess)` if the user input is valid and within the range of 1 to 100.
+/// - `Err(GuessError)` if the input cannot be read or is not a valid guess or command.
 ///
 /// # Errors
 /// This function may return the following errors:
-/// - `GuessError::ParseError`: If the input is not a valid number (e.g., non-numeric input).
-/// - `GuessError::InvalidRange`: If the parsed number is outside the valid range of 1 to 100.
-pub fn get_guess() -> Result<Guess, GuessError> {
-    println!("Please input your guess:");
+/// - `GuessError::Empty`, `GuessError::Negative`, `GuessError::Overflow` or
+///   `GuessError::NotANumber`: If the input is not a valid number (see `Parsable for Guess`).
+/// - `GuessError::OutOfRange`: If the parsed number is outside the valid range of 1 to 100.
+/// - `GuessError::UnknownCommand`: If the input starts with a colon but names no command.
+/// - `GuessError::Io`: If the input could not be read from stdin.
+/// - `GuessError::EndOfInput`: If stdin has been closed.
+pub fn get_guess<T: Primitive>(ui: &Ui, last: Option<T>) -> Result<PlayerInput<T>, GuessError<T>> {
+    get_guess_in_range(ui, Guess::MIN, Guess::MAX, last)
+}
+
+/// Prompts the user for a guess in the range `min..=max`.
+///
+/// This is `get_guess` for games played over any range; it returns the same results.
+pub fn get_guess_in_range<T: Integer>(
+    ui: &Ui,
+    min: T,
+    max: T,
+    last: Option<T>,
+) -> Result<PlayerInput<T>, GuessError<T>> {
+    println!("{}", ui.text(Role::Prompt, "prompt"));
+
+    read_guess_in_range(&mut io::stdin().lock(), min, max, last)
+}
 
+/// Reads one line from `input` and parses it into a command or a `Guess`.
+///
+/// This is the part of `get_guess` that does not print anything, which makes it usable
+/// with any buffered reader, such as a file or an in-memory buffer in tests.
+///
+/// # Arguments
+/// * `input` - The reader to take the next line from.
+/// * `last` - The value of the previous guess, which the line can refer to as `last`.
+///
+/// # Returns
+/// Returns a `Result<PlayerInput, GuessError>`:
+/// - `Ok(PlayerInput::Command)` if the line holds a command.
+/// - `Ok(PlayerInput::Guess)` if the line holds a valid guess.
+/// - `Err(GuessError::EndOfInput)` if the reader has no more data.
+/// - `Err(GuessError::Io)` if reading failed.
+/// - `Err(GuessError::UnknownCommand)` if the line looks like a command but names none.
+/// - Any error returned by `Guess::parse_with_last` for an invalid line.
+pub fn read_guess<T: Primitive, R: BufRead>(
+    input: &mut R,
+    last: Option<T>,
+) -> Result<PlayerInput<T>, GuessError<T>> {
+    read_guess_in_range(input, Guess::MIN, Guess::MAX, last)
+}
+
+/// Reads one line from `input` and parses it into a command or a `Guess` in the range
+/// `min..=max`.
+///
+/// This is `read_guess` for games played over any range; it returns the same results, with
+/// guesses parsed by `Guess::parse_in_range`.
+pub fn read_guess_in_range<T: Integer, R: BufRead>(
+    input: &mut R,
+    min: T,
+    max: T,
+    last: Option<T>,
+) -> Result<PlayerInput<T>, GuessError<T>> {
     let mut guess_str: String = String::new();
 
-    io::stdin()
-        .read_line(&mut guess_str)
-        .expect("Failed to read line");
+    if input.read_line(&mut guess_str)? == 0 {
+        return Err(GuessError::EndOfInput);
+    }
+
+    parse_player_input(&guess_str, min, max, last)
+}
+
+/// Parses a line typed by the player into a command or a `Guess` in the range `min..=max`.
+///
+/// Lines starting with a colon are commands (see `Command::parse`); anything else is parsed
+/// by `Guess::parse_in_range`.
+///
+/// # Arguments
+/// * `line` - The line typed by the player.
+/// * `min`, `max` - The smallest and largest valid guesses.
+/// * `last` - The value of the previous guess, which the line can refer to as `last`.
+pub fn parse_player_input<T: Integer>(
+    line: &str,
+    min: T,
+    max: T,
+    last: Option<T>,
+) -> Result<PlayerInput<T>, GuessError<T>> {
+    if let Some(command) = Command::parse(line) {
+        return command.map(PlayerInput::Command);
+    }
+
+    Guess::parse_in_range(line, min, max, last).map(PlayerInput::Guess)
+}
+
+/// The number of invalid guesses in a row after which the game gives up.
+///
+/// This stops a script or a stuck input source from filling the screen with errors forever.
+pub const MAX_INVALID_INPUTS: u32 = 10;
+
+/// Enum to represent how a game ended.
+///
+/// The `GameOutcome` enum is used by the game loop to report why it stopped, and maps each
+/// reason onto a distinct process exit code so scripts driving the game can tell them apart.
+///
+/// # Variants
+///
+/// - `Won`: The player guessed the secret number, or played a game without one to the end,
+///   such as a streak of higher or lower. Exit code 0.
+/// - `EndOfInput`: The input ended before the secret number was found. Exit code 3.
+/// - `InputFailed`: Reading the input failed. Exit code 4.
+/// - `TooManyInvalidInputs`: `MAX_INVALID_INPUTS` invalid guesses were made in a row. Exit code 5.
+/// - `Quit`: The player gave up with the `:q` command. Exit code 6.
+/// - `Lost`: The player ran out of chances, such as naming the wrong number at the end of
+///   twenty questions. Exit code 7.
+///
+/// Exit code 2 is reserved for invalid command-line arguments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameOutcome {
+    Won,
+    EndOfInput,
+    InputFailed,
+    TooManyInvalidInputs,
+    Quit,
+    Lost,
+}
+
+impl GameOutcome {
+    /// Returns the process exit code for this outcome.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            GameOutcome::Won => 0,
+            GameOutcome::EndOfInput => 3,
+            GameOutcome::InputFailed => 4,
+            GameOutcome::TooManyInvalidInputs => 5,
+            GameOutcome::Quit => 6,
+            GameOutcome::Lost => 7,
+        }
+    }
 
-    Guess::parse_input(&guess_str)
+    /// Returns the outcome that a fatal `GuessError` leads to, or `None` if the player
+    /// can keep guessing after the error.
+    pub fn from_error<T>(err: &GuessError<T>) -> Option<GameOutcome> {
+        match err {
+            GuessError::EndOfInput => Some(GameOutcome::EndOfInput),
+            GuessError::Io(_) => Some(GameOutcome::InputFailed),
+            _ => None,
+        }
+    }
 }
 
 /// Enum to represent the result of a user's guess.
@@ -411,19 +1298,35 @@ pub fn get_guess() -> Result<Guess, GuessError> {
 /// - `TooSmall`: Indicates the guess is too small compared to the secret number.
 /// - `TooBig`: Indicates the guess is too large compared to the secret number.
 /// - `Correct`: Indicates the guess is equal to the secret number.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GuessResult {
     TooSmall,
     TooBig,
     Correct,
 }
 
+/// Converts the `Ordering` of a guess against the secret number into a `GuessResult`.
+///
+/// This is how ordered secrets implement `Guessable::compare`, and it lets games that compare
+/// more than one value at a time, such as each coordinate of a point, give the same feedback
+/// for each of them.
+impl From<Ordering> for GuessResult {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Less => GuessResult::TooSmall,
+            Ordering::Greater => GuessResult::TooBig,
+            Ordering::Equal => GuessResult::Correct,
+        }
+    }
+}
+
 /// Compares the user's guess with the secret number and returns the result.
 ///
-/// This function compares the provided `guess` with the `secret_number` and returns
-/// a `GuessResult` indicating whether the guess is too small, too large, or correct.
-/// It does not print anything to the console, but the result can be used to give
-/// appropriate feedback to the user (e.g., by printing a message).
+/// This function compares the provided `guess` with the `secret_number` and returns the
+/// feedback of the `Guessable` type: for ordered secrets, a `GuessResult` indicating whether
+/// the guess is too small, too large, or correct. It does not print anything to the console,
+/// but the result can be used to give appropriate feedback to the user (e.g., by printing a
+/// message).
 ///
 /// # Arguments
 ///
@@ -436,30 +1339,66 @@ pub enum GuessResult {
 ///
 /// # Returns
 ///
-/// Returns a `GuessResult`:
+/// Returns the `Guessable::Feedback` of the guess. For a `GuessResult`, that is:
 ///
 /// - `GuessResult::TooSmall` if the guess is smaller than the secret number.
 /// - `GuessResult::TooBig` if the guess is larger than the secret number.
 /// - `GuessResult::Correct` if the guess matches the secret number.
 ///
+/// For a point, it is a `PointComparison` with the result for each axis and the distance to
+/// the secret point.
+///
 /// # Notes
 ///
 /// The function assumes that both `guess` and `secret_number` are of types that implement
 /// the `Guessable` trait, which defines the `compare` method for comparing the two values.
-/// The result of the comparison is then used to determine which of the `GuessResult` variants
-/// should be returned.
-pub fn handle_guess<G: Guessable>(guess: G, secret_number: &G) -> GuessResult {
-    match guess.compare(secret_number) {
-        Ordering::Less => GuessResult::TooSmall,
-        Ordering::Greater => GuessResult::TooBig,
-        Ordering::Equal => GuessResult::Correct,
+/// The result of the comparison is the feedback that is returned.
+pub fn handle_guess<G: Guessable>(guess: G, secret_number: &G) -> G::Feedback {
+    guess.compare(secret_number)
+}
+
+/// Enum to represent the answer to a yes-or-no question about the secret.
+///
+/// # Variants
+///
+/// - `Yes`: The secret has the property the question asks about.
+/// - `No`: The secret does not have it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Answer {
+    Yes,
+    No,
+}
+
+impl From<bool> for Answer {
+    fn from(matches: bool) -> Self {
+        if matches {
+            Answer::Yes
+        } else {
+            Answer::No
+        }
     }
 }
 
+/// Asks a yes-or-no question about the secret and returns the answer.
+///
+/// This is `handle_guess` for games played with questions instead of guesses, such as
+/// asking whether the secret is in a set of numbers. Like `handle_guess`, it prints nothing.
+///
+/// # Arguments
+///
+/// * `query` - The question, which must implement the `Query` trait for the secret's type.
+/// * `secret` - The secret the question is about.
+pub fn handle_query<T, Q: Query<T>>(query: &Q, secret: &T) -> Answer {
+    query.matches(secret).into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // Most tests play the classic game, which guesses `u32`s.
+    type Guess = super::Guess<u32>;
+
     // Test for parsing a valid input string into a Guess
     #[test]
     fn parse_input_valid() {
@@ -478,11 +1417,11 @@ mod tests {
         let result = Guess::parse_input(invalid_input); // Try to parse the invalid input
                                                         // Check if the result is Err, meaning the input could not be parsed
         assert!(result.is_err(), "Invalid input should result in an error");
-        // Specifically check for a ParseError, although we don't care about the exact details here
-        if let Err(GuessError::ParseError(_)) = result {
-            // No additional assertions needed; we just want to check for the error
+        // Specifically check for a NotANumber error that keeps the rejected input
+        if let Err(GuessError::NotANumber { input, .. }) = result {
+            assert_eq!(input, "not_a_number");
         } else {
-            panic!("Expected ParseError"); // Panic if the error type is not what we expect
+            panic!("Expected NotANumber"); // Panic if the error type is not what we expect
         }
     }
 
@@ -491,8 +1430,212 @@ mod tests {
     fn parse_input_out_of_range() {
         let out_of_range_input = "150"; // Input exceeds the valid range
         let result = Guess::parse_input(out_of_range_input); // Try to parse the input
-                                                             // Check if the result is an Err with InvalidRange error
-        assert_eq!(result, Err(GuessError::InvalidRange));
+                                                             // Check if the result is an Err with OutOfRange error
+        assert_eq!(
+            result,
+            Err(GuessError::OutOfRange {
+                value: 150,
+                min: 1,
+                max: 100
+            })
+        );
+    }
+
+    // Test that blank, negative and huge inputs get their own errors
+    #[test]
+    fn parse_input_precise_errors() {
+        assert_eq!(Guess::parse_input("  \n"), Err(GuessError::Empty));
+        assert_eq!(
+            Guess::parse_input("-5"),
+            Err(GuessError::Negative {
+                input: "-5".to_string()
+            })
+        );
+        assert_eq!(
+            Guess::parse_input("99999999999"),
+            Err(GuessError::Overflow {
+                input: "99999999999".to_string()
+            })
+        );
+        // A lone minus sign is not a negative number
+        assert!(matches!(
+            Guess::parse_input("-"),
+            Err(GuessError::NotANumber { .. })
+        ));
+    }
+
+    // Test that numbers written in words are accepted
+    #[test]
+    fn parse_input_words() {
+        assert_eq!(Guess::parse_input("forty two\n").unwrap().value(), 42);
+        assert_eq!(Guess::parse_input("Seventy-Five").unwrap().value(), 75);
+        assert_eq!(Guess::parse_input("one hundred").unwrap().value(), 100);
+        assert_eq!(Guess::parse_input("dix-sept").unwrap().value(), 17);
+        assert_eq!(
+            Guess::parse_input("two hundred"),
+            Err(GuessError::OutOfRange {
+                value: 200,
+                min: 1,
+                max: 100
+            })
+        );
+        assert_eq!(
+            Guess::parse_input("five six"),
+            Err(GuessError::AmbiguousNumber {
+                input: "five six".to_string()
+            })
+        );
+        assert!(matches!(
+            Guess::parse_input("a"),
+            Err(GuessError::NotANumber { .. })
+        ));
+        assert_eq!(
+            Guess::parse_input("one hundred and"),
+            Err(GuessError::AmbiguousNumber {
+                input: "one hundred and".to_string()
+            })
+        );
+    }
+
+    // Test that arithmetic expressions are evaluated before the range check
+    #[test]
+    fn parse_input_expressions() {
+        assert_eq!(Guess::parse_input("(37+62)/2").unwrap().value(), 49);
+        assert_eq!(Guess::parse_input("2^6").unwrap().value(), 64);
+        assert_eq!(
+            Guess::parse_with_last("last+10", Some(40)).unwrap().value(),
+            50
+        );
+        assert_eq!(
+            Guess::parse_input("last+10"),
+            Err(GuessError::NoPreviousGuess)
+        );
+        assert_eq!(
+            Guess::parse_input("10/(5-5)"),
+            Err(GuessError::DivisionByZero {
+                input: "10/(5-5)".to_string()
+            })
+        );
+        assert_eq!(
+            Guess::parse_input("2^99"),
+            Err(GuessError::ExpressionOverflow {
+                input: "2^99".to_string()
+            })
+        );
+        assert_eq!(
+            Guess::parse_input("2^40"),
+            Err(GuessError::Overflow {
+                input: "2^40".to_string()
+            })
+        );
+        assert_eq!(
+            Guess::parse_input("5-10"),
+            Err(GuessError::Negative {
+                input: "5-10".to_string()
+            })
+        );
+        assert_eq!(
+            Guess::parse_input("3*50"),
+            Err(GuessError::OutOfRange {
+                value: 150,
+                min: 1,
+                max: 100
+            })
+        );
+        assert_eq!(
+            Guess::parse_input("(4"),
+            Err(GuessError::InvalidExpression {
+                input: "(4".to_string()
+            })
+        );
+    }
+
+    // Test guessing signed and very large numbers in custom ranges
+    #[test]
+    fn parse_in_range_other_types() {
+        let guess = super::Guess::parse_in_range("-250", -500i32, 500, None).unwrap();
+        assert_eq!(guess.value(), -250);
+        assert_eq!(
+            super::Guess::parse_in_range("-501", -500i32, 500, None),
+            Err(GuessError::OutOfRange {
+                value: -501,
+                min: -500,
+                max: 500
+            })
+        );
+        assert_eq!(
+            super::Guess::parse_in_range("last-100", -500i64, 500, Some(-350))
+                .unwrap()
+                .value(),
+            -450
+        );
+        assert_eq!(
+            super::Guess::parse_in_range("-1000", i8::MIN, i8::MAX, None),
+            Err(GuessError::Overflow {
+                input: "-1000".to_string()
+            })
+        );
+        assert_eq!(
+            super::Guess::parse_in_range("two hundred", i8::MIN, i8::MAX, None),
+            Err(GuessError::Overflow {
+                input: "two hundred".to_string()
+            })
+        );
+
+        let max = 1_000_000_000_000_000u64;
+        let guess = super::Guess::parse_in_range("999999999999999", 0, max, None).unwrap();
+        assert_eq!(guess.value(), 999_999_999_999_999);
+        assert_eq!(super::Guess::<u128>::new(100).unwrap().value(), 100);
+        assert_eq!(
+            super::Guess::<i16>::parse_input("forty two")
+                .unwrap()
+                .value(),
+            42
+        );
+    }
+
+    // Test guessing in impossible mode, with numbers of up to 101 digits
+    #[test]
+    fn parse_big_guesses() {
+        let (min, max) = super::Guess::impossible_range();
+        let googol = format!("1{}", "0".repeat(100));
+        let guess = super::Guess::<BigInt>::parse_input(&googol).unwrap();
+        assert_eq!(guess.value(), max);
+        assert_eq!(
+            guess.compare(&super::Guess::parse_input("42").unwrap()),
+            GuessResult::TooBig
+        );
+        assert_eq!(
+            super::Guess::<BigInt>::parse_input(&format!("{}1", googol)),
+            Err(GuessError::OutOfRange {
+                value: BigInt::parse_digits(&format!("{}1", googol)).unwrap(),
+                min: min.clone(),
+                max: max.clone()
+            })
+        );
+        assert_eq!(
+            super::Guess::parse_in_range("last+1", min.clone(), max.clone(), Some(max.clone())),
+            Err(GuessError::ExpressionOverflow {
+                input: "last+1".to_string()
+            })
+        );
+        assert!(matches!(
+            super::Guess::<BigInt>::parse_input("1e100"),
+            Err(GuessError::InvalidExpression { .. })
+        ));
+        let secret = get_secret_number(min.clone(), max.clone());
+        assert!(min <= secret && secret <= max);
+    }
+
+    // Test that secret numbers can be drawn from any integer range
+    #[test]
+    fn get_secret_number_generic() {
+        for _ in 0..100 {
+            assert!((-500..=500).contains(&get_secret_number(-500i32, 500)));
+            let big = get_secret_number(0u64, 1_000_000_000_000_000);
+            assert!(big <= 1_000_000_000_000_000);
+        }
+        assert_eq!(get_secret_number(i128::MIN, i128::MIN), i128::MIN);
     }
 
     // Test for creating a Guess with a valid value within the range
@@ -507,10 +1650,14 @@ mod tests {
     #[test]
     fn guess_creation_invalid_range_low() {
         let guess = Guess::new(0); // Guess value is too low (below 1)
-                                   // Check if the result is Err with InvalidRange error
+                                   // Check if the result is Err with OutOfRange error
         assert_eq!(
             guess,
-            Err(GuessError::InvalidRange),
+            Err(GuessError::OutOfRange {
+                value: 0,
+                min: 1,
+                max: 100
+            }),
             "Guess below 1 should be invalid"
         );
     }
@@ -519,10 +1666,14 @@ mod tests {
     #[test]
     fn guess_creation_invalid_range_high() {
         let guess = Guess::new(101); // Guess value is too high (above 100)
-                                     // Check if the result is Err with InvalidRange error
+                                     // Check if the result is Err with OutOfRange error
         assert_eq!(
             guess,
-            Err(GuessError::InvalidRange),
+            Err(GuessError::OutOfRange {
+                value: 101,
+                min: 1,
+                max: 100
+            }),
             "Guess above 100 should be invalid"
         );
     }
@@ -557,6 +1708,88 @@ mod tests {
         assert_eq!(result, GuessResult::TooBig);
     }
 
+    // Test that errors can be displayed and expose their source
+    #[test]
+    fn guess_error_display_and_source() {
+        let parse_error = Guess::parse_input("abc").unwrap_err();
+        assert_eq!(parse_error.to_string(), "'abc' is not a number");
+        assert!(parse_error.source().is_some());
+
+        let range_error = Guess::new(150).unwrap_err();
+        assert_eq!(range_error.to_string(), "150 is above the maximum of 100");
+        assert!(range_error.source().is_none());
+        assert_eq!(
+            Guess::new(0).unwrap_err().to_string(),
+            "0 is below the minimum of 1"
+        );
+
+        let io_error: GuessError = GuessError::from(io::Error::other("closed"));
+        assert_eq!(io_error.to_string(), "failed to read input: closed");
+        assert!(io_error.source().is_some());
+
+        let date_error: GuessError = GuessError::Invalid {
+            input: "tomorrow".to_string(),
+            kind: InputKind::Date,
+        };
+        assert_eq!(date_error.to_string(), "'tomorrow' is not a valid date");
+    }
+
+    // Test that error messages are written to the supplied sink
+    #[test]
+    fn handle_error_writes_to_sink() {
+        let ui = Ui::new(theme::Theme::plain(), i18n::Locale::English);
+        let mut out = Vec::new();
+        Guess::new(150)
+            .unwrap_err()
+            .handle_error(&ui, &mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Error: 150 is above the maximum of 100.\n"
+        );
+    }
+
+    // Test that reading stops cleanly at the end of the input
+    #[test]
+    fn read_guess_until_end_of_input() {
+        let mut input = io::Cursor::new("42\n:hint\nabc\n");
+        assert_eq!(
+            read_guess(&mut input, None).unwrap(),
+            PlayerInput::Guess(Guess::new(42).unwrap())
+        );
+        assert_eq!(
+            read_guess(&mut input, None::<u32>).unwrap(),
+            PlayerInput::Command(Command::Hint)
+        );
+        assert!(matches!(
+            read_guess(&mut input, None::<u32>),
+            Err(GuessError::NotANumber { .. })
+        ));
+        let end = read_guess(&mut input, None::<u32>).unwrap_err();
+        assert_eq!(end, GuessError::EndOfInput);
+        assert!(end.is_fatal());
+        assert_eq!(GameOutcome::from_error(&end), Some(GameOutcome::EndOfInput));
+        assert_eq!(GameOutcome::from_error(&GuessError::<u32>::Empty), None);
+    }
+
+    // Test that every outcome has its own exit code
+    #[test]
+    fn game_outcome_exit_codes() {
+        let outcomes = [
+            GameOutcome::Won,
+            GameOutcome::EndOfInput,
+            GameOutcome::InputFailed,
+            GameOutcome::TooManyInvalidInputs,
+            GameOutcome::Quit,
+            GameOutcome::Lost,
+        ];
+        let mut codes: Vec<i32> = outcomes.iter().map(GameOutcome::exit_code).collect();
+        assert_eq!(codes[0], 0);
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), outcomes.len());
+    }
+
     // Test for the initial value of the guess count
     #[test]
     fn test_guess_count_initialization() {
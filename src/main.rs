@@ -1,60 +1,159 @@
 use guessing_game::{
-    get_guess, get_secret_number, handle_guess, ErrorHandler, Guess, GuessCount, GuessResult,
-    Incrementable,
+    batch, circle, classic, date, decimal, delay, dictionary, drift, group,
+    integer::Integer,
+    open,
+    options::Options,
+    party::{self, DEFAULT_ROUNDS},
+    point,
+    prompt::Prompter,
+    question, secrets, streak,
+    theme::{self, Role},
+    ui::Ui,
+    GameOutcome,
+};
+use num_bigint::BigInt;
+use std::{
+    io::{self, BufRead, Write},
+    process,
 };
 
-/// The main function that runs the game loop for guessing the secret number.
+/// The main function that sets up the game and hands it to the chosen game mode.
 ///
-/// This is the entry point of the "Guess the Number" game. The function handles the entire
-/// game flow, including generating a secret number, prompting the user for guesses,
-/// and comparing each guess to the secret number. The game continues until the user guesses
-/// correctly, at which point it prints a victory message and ends.
+/// This is the entry point of the "Guess the Number" game. The function reads the settings,
+/// picks the game mode they ask for and exits with the code of the `GameOutcome` once the
+/// game is over. The game loops themselves live in the module of each mode.
 ///
 /// # Flow
-/// 1. The game generates a random secret number between 1 and 100 using the `get_secret_number` function.
-/// 2. It initializes a `GuessCount` to track the number of guesses the player has made.
-/// 3. The game enters a loop where it:
-///    - Prompts the user to input a guess using the `get_guess` function.
-///    - Validates and parses the input, handling any errors (e.g., invalid input or out-of-range guesses).
-///    - Compares the guess to the secret number using the `handle_guess` function.
-///    - Increments the guess count with each attempt.
-/// 4. The loop continues until the user guesses correctly, at which point a success message is printed
-///    and the game ends.
+/// 1. The command-line arguments are parsed into `Options`, which select the color theme and
+///    the language. Colors are switched off automatically when stdout is not a terminal or
+///    `NO_COLOR` is set, and the language defaults to the one in `LANG`.
+/// 2. A `Prompter` is created on stdin and stdout, and every game reads and prints through it.
+/// 3. Games whose secret is not a whole number are played right away: with `--decimals` or
+///    `--tolerance` by `decimal::play`, with `--dimensions` or `--rgb` by `point::play`, with
+///    `--word` or `--dictionary` by `dictionary::play`, with `--open` by `open::play`, with
+///    `--twenty-questions` by `question::play` and with `--date` or `--years` by `date::play`.
+/// 4. Every other game is played by `play`, with `i64` numbers, or with `BigInt`s when the
+///    range chosen with `--min`, `--max` or `--impossible` does not fit in an `i64`.
+/// 5. If the game did not end with a win, the program exits with the code of the
+///    `GameOutcome`.
 fn main() {
-    println!("Guess the number");
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            process::exit(2);
+        }
+    };
+    let ui = Ui::new(
+        options.resolved_theme(theme::colors_supported()),
+        options.resolved_locale(),
+    );
 
-    // Generate a random secret number between 1 and 100. we use the Guess for the secret
-    // number to make it easier to compare the users input Guess against the secret number
-    // using the `.compare` trait in the `handle_guess` method.
-    let secret_number =
-        Guess::new(get_secret_number(1, 100)).expect("Failed to generate secret number");
+    // Load the word list first, so a missing file is reported like a bad argument.
+    let dictionary = options.words.as_ref().map(|words| match words.load() {
+        Ok(dictionary) if !dictionary.is_empty() => dictionary,
+        Ok(_) => {
+            eprintln!("Error: the word list has no words");
+            process::exit(2);
+        }
+        Err(e) => {
+            eprintln!("Error: could not read the word list: {}", e);
+            process::exit(2);
+        }
+    });
 
-    // Initialize the guess count to track the number of attempts.
-    let mut guess_count = GuessCount::new();
+    // Ask for input until it is valid, giving up when it ends or never gets better.
+    let mut prompter = Prompter::new(&ui, io::stdin().lock(), io::stdout());
+    prompter.say(&ui.text(Role::Headline, "headline"));
 
-    // Game loop: continue until the user guesses correctly.
-    loop {
-        // Get the user's guess and handle any errors (invalid input or parsing errors).
-        let guess = match get_guess() {
-            Ok(g) => g,
-            Err(err) => {
-                // Handle input error (e.g., out of range or invalid input).
-                err.handle_error();
-                continue; // Ask for a new guess if there was an error.
-            }
-        };
+    let month_first = ui.catalog.locale().month_first();
+    let special = if let Some(game) = options.decimal_game() {
+        Some(decimal::play(&mut prompter, &game))
+    } else if let Some(game) = options.point_game() {
+        Some(point::play(&mut prompter, &game))
+    } else if let Some(dictionary) = dictionary {
+        Some(dictionary::play(&mut prompter, &dictionary))
+    } else if options.open {
+        Some(open::play(&mut prompter, &options.min))
+    } else if let Some(game) = options.question_game() {
+        Some(question::play(&mut prompter, &game))
+    } else {
+        options
+            .date_game(month_first)
+            .map(|game| date::play(&mut prompter, &game))
+    };
+    if let Some(outcome) = special {
+        if outcome != GameOutcome::Won {
+            process::exit(outcome.exit_code());
+        }
+        return;
+    }
 
-        // Increment the guess count after each guess.
-        guess_count.increment();
+    // Tell the player the range when it is not the usual one. With several secrets, the
+    // introduction already gives the range.
+    let defaults = Options::default();
+    if options.secrets.is_none() && (&options.min, &options.max) != (&defaults.min, &defaults.max) {
+        prompter.say(&ui.format(
+            Role::Info,
+            "range",
+            &[("min", &options.min), ("max", &options.max)],
+        ));
+    }
 
-        // Compare the guess to the secret number and check if the user wins.
-        match handle_guess(guess, &secret_number) {
-            GuessResult::TooSmall => println!("Too small"),
-            GuessResult::TooBig => println!("Too big"),
-            GuessResult::Correct => {
-                println!("You win, in {} guesses!", guess_count.value());
-                break;
-            }
-        }
+    // Play with machine integers when they are big enough, since they are much faster.
+    let outcome = match (options.min.to_i64(), options.max.to_i64()) {
+        (Some(min), Some(max)) => play(&mut prompter, &options, min, max),
+        _ => play::<BigInt, _, _>(
+            &mut prompter,
+            &options,
+            options.min.clone(),
+            options.max.clone(),
+        ),
+    };
+    if outcome != GameOutcome::Won {
+        process::exit(outcome.exit_code());
+    }
+}
+
+/// Plays one game over the range `min..=max` in the mode chosen with `options` and returns
+/// how it ended.
+///
+/// The modes work with any integer type, so the same game can be played with `i64` numbers or
+/// with `BigInt`s. With `--secrets`, the game is played by `secrets::play`, with `--drift` by
+/// `drift::play`, with `--group` by `group::play`, with `--batch` by `batch::play`, with
+/// `--circle` by `circle::play`, with `--delay` or `--feedback-every` by `delay::play`, with
+/// `--streak` by `streak::play` and with `--party` by `party::play`. Otherwise it is the
+/// classic game, played by `classic::play`.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output.
+/// * `options` - The settings chosen on the command line.
+/// * `min`, `max` - The smallest and largest possible secret number.
+fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    options: &Options,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    if let Some(count) = options.secrets {
+        secrets::play(prompter, options.secrets_feedback, count, min, max)
+    } else if let Some(drift) = options.drift {
+        let feedback = options.drift_feedback.unwrap_or_default();
+        drift::play(prompter, drift, feedback, options.seed, min, max)
+    } else if options.group {
+        group::play(prompter, min, max)
+    } else if let Some(size) = options.batch {
+        batch::play(prompter, size, min, max)
+    } else if options.circle {
+        circle::play(prompter, min, max)
+    } else if let Some(delay) = options.delay {
+        delay::play(prompter, delay, min, max)
+    } else if options.streak {
+        streak::play(prompter, min, max)
+    } else if let Some(players) = options.party {
+        let rounds = options.rounds.unwrap_or(DEFAULT_ROUNDS);
+        party::play(prompter, players, rounds, min, max)
+    } else {
+        classic::play(prompter, options.skip_wasted, min, max)
     }
 }
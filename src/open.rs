@@ -0,0 +1,345 @@
+use crate::{
+    command::Command, integer::Integer, prompt::Prompter, theme::Role, GameOutcome, Guess,
+    GuessCount, GuessError, GuessResult, Incrementable, PlayerInput,
+};
+use num_bigint::BigInt;
+use rand::Rng;
+use std::io::{BufRead, Write};
+
+/// The scale of the secret numbers of the open-ended game.
+///
+/// A secret is more than `n` above the minimum with a probability of about
+/// `SCALE / (SCALE + n)`, so half of them are within 100 of it, one in ten is more than 900
+/// above it and one in ten thousand is about a million above it.
+pub const SCALE: u32 = 100;
+
+/// Draws a secret number of at least `min` with no upper bound.
+///
+/// The secret follows a shifted Pareto distribution, whose tail is so heavy that no bound
+/// is ever safe to assume: `min + SCALE / u - SCALE`, with the division rounded down, for a
+/// uniform `u` in `(0, 1]`. The largest possible secret is about 10^18 above `min`.
+pub fn open_secret(min: &BigInt) -> BigInt {
+    // `gen` gives a value in `[0, 1)`, so `1 - gen` is never zero.
+    let u = 1.0 - rand::thread_rng().gen::<f64>();
+    let offset = (f64::from(SCALE) / u).floor() as u64 - u64::from(SCALE);
+    min + BigInt::from(offset)
+}
+
+/// Parses a line typed by the player into a command or a `Guess` of at least `min`.
+///
+/// This is `parse_player_input` for the open-ended game: lines starting with a colon are
+/// commands (see `Command::parse`), and anything else is parsed by `Guess::parse_at_least`.
+pub fn parse_open_input<T: Integer>(
+    line: &str,
+    min: T,
+    last: Option<T>,
+) -> Result<PlayerInput<T>, GuessError<T>> {
+    if let Some(command) = Command::parse(line) {
+        return command.map(PlayerInput::Command);
+    }
+
+    Guess::parse_at_least(line, min, last).map(PlayerInput::Guess)
+}
+
+/// A struct tracking a search for a secret number with no known upper bound.
+///
+/// An open-ended search has two phases: while every guess is too small, the player only
+/// knows a lower bound and has to grow their guesses until one overshoots; after that the
+/// secret lies in a finite interval, which can be halved like in the classic game. The
+/// `OpenSearch` struct records the guesses of both phases. It can make the guesses of the
+/// doubling strategy itself, and recognize whether the player followed that strategy.
+///
+/// # Fields
+/// - `min`: The smallest possible secret number.
+/// - `lower`: The smallest value the secret number can still be.
+/// - `upper`: The largest value the secret number can still be, once a guess was too big.
+/// - `guesses`: The values guessed so far with their results, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenSearch {
+    min: BigInt,
+    lower: BigInt,
+    upper: Option<BigInt>,
+    guesses: Vec<(BigInt, GuessResult)>,
+}
+
+impl OpenSearch {
+    /// Creates a search for a secret number of at least `min`.
+    pub fn new(min: BigInt) -> OpenSearch {
+        OpenSearch {
+            lower: min.clone(),
+            min,
+            upper: None,
+            guesses: Vec::new(),
+        }
+    }
+
+    /// Narrows the search with the feedback for a guess.
+    pub fn record(&mut self, value: BigInt, result: GuessResult) {
+        match result {
+            GuessResult::TooSmall => self.lower = self.lower.clone().max(&value + 1),
+            GuessResult::TooBig => {
+                let bound = &value - 1;
+                self.upper = Some(match self.upper.take() {
+                    Some(upper) => upper.min(bound),
+                    None => bound,
+                });
+            }
+            GuessResult::Correct => {
+                self.lower = value.clone();
+                self.upper = Some(value.clone());
+            }
+        }
+        self.guesses.push((value, result));
+    }
+
+    /// Returns the smallest value the secret number can still be, and the largest one if a
+    /// guess has been too big.
+    pub fn bounds(&self) -> (BigInt, Option<BigInt>) {
+        (self.lower.clone(), self.upper.clone())
+    }
+
+    /// Returns the guess the doubling strategy makes next.
+    ///
+    /// Without an upper bound, the guess is as far above the lower bound as the lower bound
+    /// is above `min`, which doubles the part of the range ruled out with every guess:
+    /// from 1, it guesses 1, 3, 7, 15 and so on. Once a guess was too big, it guesses the
+    /// middle of the remaining interval.
+    pub fn next_guess(&self) -> BigInt {
+        match &self.upper {
+            Some(upper) => BigInt::middle(&self.lower, upper),
+            None => &self.lower + (&self.lower - &self.min),
+        }
+    }
+
+    /// Returns the number of guesses made before an upper bound was known, counting the guess
+    /// that overshot or found the secret.
+    pub fn expanding_guesses(&self) -> usize {
+        self.guesses
+            .iter()
+            .position(|(_, result)| *result != GuessResult::TooSmall)
+            .map_or(self.guesses.len(), |index| index + 1)
+    }
+
+    /// Checks whether the player searched for an upper bound by doubling.
+    ///
+    /// A search counts as doubling when it took at least three guesses to find an upper
+    /// bound, and each of those guesses ruled out at least twice as many numbers as the one
+    /// before, like 1, 2, 4, 8 or 10, 100, 1000.
+    pub fn is_doubling(&self) -> bool {
+        let expanding = self.expanding_guesses();
+        if expanding < 3 {
+            return false;
+        }
+        let span = |value: &BigInt| value - &self.min + 1;
+        self.guesses[..expanding]
+            .windows(2)
+            .all(|pair| span(&pair[1].0) >= span(&pair[0].0) * 2)
+    }
+
+    /// Returns the number of guesses the doubling strategy takes to find `secret`, starting
+    /// from `min`.
+    pub fn doubling_guesses(min: &BigInt, secret: &BigInt) -> u32 {
+        let mut search = OpenSearch::new(min.clone());
+        loop {
+            let guess = search.next_guess();
+            let result = GuessResult::from(guess.cmp(secret));
+            search.record(guess, result);
+            if result == GuessResult::Correct {
+                return search.guesses.len() as u32;
+            }
+        }
+    }
+}
+
+/// Plays one game with a secret number of at least `min` and no maximum, and returns how it
+/// ended.
+///
+/// The secret is drawn by `open_secret`, so it is usually small but can be huge, and the
+/// player has to find an upper bound before they can close in on it. Guesses have no maximum
+/// and can be as large as the player likes. `:hint` and `:range` work from what the feedback
+/// has shown so far, and `:hint` follows the doubling strategy of `OpenSearch`. When the game
+/// is won, the player is told whether they searched by doubling and how many guesses that
+/// strategy takes for this secret.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `min` - The smallest possible secret number.
+pub fn play<R: BufRead, W: Write>(prompter: &mut Prompter<R, W>, min: &BigInt) -> GameOutcome {
+    let ui = prompter.ui();
+    prompter.say(&ui.format(Role::Info, "open-intro", &[("min", min)]));
+
+    let secret = open_secret(min);
+    let mut search = OpenSearch::new(min.clone());
+    let mut last = None;
+    let mut guess_count = GuessCount::new();
+
+    let outcome = loop {
+        let input = match prompter.next(|line| parse_open_input(line, min.clone(), last.clone())) {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let guess = match input {
+            PlayerInput::Guess(guess) => guess,
+            PlayerInput::Command(Command::Quit) => break GameOutcome::Quit,
+            PlayerInput::Command(Command::Hint) => {
+                let value = search.next_guess();
+                match search.bounds() {
+                    (lower, Some(upper)) => prompter.say(&ui.format(
+                        Role::Info,
+                        "hint",
+                        &[("value", &value), ("min", &lower), ("max", &upper)],
+                    )),
+                    (_, None) => {
+                        prompter.say(&ui.format(Role::Info, "open-hint", &[("value", &value)]))
+                    }
+                }
+                continue;
+            }
+            PlayerInput::Command(Command::Range) => {
+                match search.bounds() {
+                    (lower, Some(upper)) => prompter.say(&ui.format(
+                        Role::Info,
+                        "range",
+                        &[("min", &lower), ("max", &upper)],
+                    )),
+                    (lower, None) => {
+                        prompter.say(&ui.format(Role::Info, "open-range", &[("min", &lower)]))
+                    }
+                }
+                continue;
+            }
+            PlayerInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+        let value = guess.value();
+
+        guess_count.increment();
+        let result = GuessResult::from(value.cmp(&secret));
+        search.record(value.clone(), result);
+        last = Some(value);
+        match result {
+            GuessResult::TooSmall => prompter.say(&ui.text(Role::TooSmall, "too-small")),
+            GuessResult::TooBig => prompter.say(&ui.text(Role::TooBig, "too-big")),
+            GuessResult::Correct => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                break GameOutcome::Won;
+            }
+        }
+    };
+
+    if outcome == GameOutcome::Won {
+        if search.is_doubling() {
+            prompter.say(&ui.text(Role::Info, "search-doubling"));
+        }
+        let ideal = u64::from(OpenSearch::doubling_guesses(min, &secret));
+        prompter.say(&ui.plural(Role::Info, "search-ideal", ideal, &[]));
+    } else {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(Role::Headline, "reveal", count, &[("secret", &secret)]));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(value: i64) -> BigInt {
+        BigInt::from(value)
+    }
+
+    // Test that secrets are never below the minimum, and usually small
+    #[test]
+    fn secrets_are_heavy_tailed() {
+        let secrets: Vec<BigInt> = (0..1000).map(|_| open_secret(&big(1))).collect();
+        assert!(secrets.iter().all(|secret| *secret >= big(1)));
+        let small = secrets
+            .iter()
+            .filter(|&secret| *secret <= big(1000))
+            .count();
+        assert!(small > 800);
+        assert!(open_secret(&big(-50)) >= big(-50));
+    }
+
+    // Test that guesses have a minimum and no maximum
+    #[test]
+    fn parse_without_maximum() {
+        let huge = "1".repeat(40);
+        assert_eq!(
+            Guess::parse_at_least(&huge, big(1), None).map(|guess| guess.value()),
+            Ok(huge.parse().unwrap())
+        );
+        assert_eq!(
+            Guess::parse_at_least("0", big(1), None),
+            Err(GuessError::BelowMinimum {
+                value: big(0),
+                min: big(1)
+            })
+        );
+        assert_eq!(
+            Guess::parse_at_least("last*2", 1u32, Some(300)).map(|guess| guess.value()),
+            Ok(600)
+        );
+        assert_eq!(
+            parse_open_input(":q", big(1), None),
+            Ok(PlayerInput::Command(Command::Quit))
+        );
+    }
+
+    // Test the doubling strategy, first growing and then halving
+    #[test]
+    fn doubling_strategy() {
+        let mut search = OpenSearch::new(big(1));
+        let mut guesses = Vec::new();
+        loop {
+            let guess = search.next_guess();
+            let result = GuessResult::from(guess.cmp(&big(37)));
+            guesses.push(guess.clone());
+            search.record(guess, result);
+            if result == GuessResult::Correct {
+                break;
+            }
+        }
+        assert_eq!(guesses, [1, 3, 7, 15, 31, 63, 47, 39, 35, 37].map(big));
+        assert_eq!(search.bounds(), (big(37), Some(big(37))));
+        assert_eq!(OpenSearch::doubling_guesses(&big(1), &big(37)), 10);
+        assert_eq!(OpenSearch::doubling_guesses(&big(1), &big(1)), 1);
+    }
+
+    // Test recognizing how a player searched
+    #[test]
+    fn recognize_doubling() {
+        let play = |guesses: &[(i64, GuessResult)]| {
+            let mut search = OpenSearch::new(big(1));
+            for &(value, result) in guesses {
+                search.record(big(value), result);
+            }
+            search
+        };
+        use GuessResult::*;
+        let doubling = play(&[
+            (10, TooSmall),
+            (100, TooSmall),
+            (1000, TooBig),
+            (500, TooBig),
+        ]);
+        assert_eq!(doubling.expanding_guesses(), 3);
+        assert!(doubling.is_doubling());
+        let linear = play(&[
+            (100, TooSmall),
+            (200, TooSmall),
+            (300, TooSmall),
+            (400, TooBig),
+        ]);
+        assert_eq!(linear.expanding_guesses(), 4);
+        assert!(!linear.is_doubling());
+        let lucky = play(&[(50, TooSmall), (5000, TooBig)]);
+        assert!(!lucky.is_doubling());
+        assert_eq!(lucky.bounds(), (big(51), Some(big(4999))));
+    }
+}
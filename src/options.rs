@@ -0,0 +1,1080 @@
+use crate::{
+    date::{Date, DateGame},
+    decimal::{self, Decimal, DecimalGame},
+    delay::{Delay, MAX_DELAY},
+    dictionary::WordList,
+    drift::{DriftFeedback, MAX_DRIFT},
+    i18n::Locale,
+    party::{MAX_PLAYERS, MAX_ROUNDS},
+    point::{self, FeedbackMode, PointGame},
+    question::{QuestionGame, DEFAULT_QUESTIONS},
+    secrets::{SecretsFeedback, MAX_SECRETS},
+    theme::Theme,
+    Guess,
+};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+/// A struct holding the settings chosen on the command line.
+///
+/// The `Options` struct is built from the program arguments by `Options::parse` and tells
+/// the game how it should behave. Every setting has a sensible default, so running the
+/// game without any arguments plays the classic game.
+///
+/// # Fields
+/// - `theme`: The color theme used for all output.
+/// - `color`: Whether colors were explicitly requested (`Some(true)`), explicitly disabled
+///   (`Some(false)`), or should be detected automatically (`None`).
+/// - `locale`: The language chosen with `--lang`, or `None` to use the environment.
+/// - `skip_wasted`: Whether wasted guesses, which cannot tell the player anything new, are
+///   left out of the guess count.
+/// - `min`, `max`: The range the secret number is drawn from, 1 to 100 unless changed with
+///   `--min`, `--max` or `--impossible`. The bounds can be arbitrarily large.
+/// - `decimals`: The number of decimal places of the secret number chosen with `--decimals`,
+///   or `None` to guess whole numbers.
+/// - `tolerance`: How far from the secret number a guess may be and still be correct, chosen
+///   with `--tolerance`, or `None` if guesses must be exact.
+/// - `dimensions`: The number of coordinates of the secret point chosen with `--dimensions`,
+///   or `None` to guess a single number.
+/// - `rgb`: Whether the secret is a color, chosen with `--rgb`.
+/// - `feedback`: How the player is told where a secret point or color is, chosen with
+///   `--feedback`, or `None` for the default `FeedbackMode`.
+/// - `words`: Where the words come from when the secret is a word, chosen with `--word` or
+///   `--dictionary`, or `None` if the secret is not a word.
+/// - `years`: The first and last year of the secret date, chosen with `--date` or `--years`,
+///   or `None` if the secret is not a date.
+/// - `date_hints`: Whether wrong dates also say if the year or the month is right.
+/// - `secrets`: The number of secret numbers hidden at once, chosen with `--secrets`, or
+///   `None` for a single secret number.
+/// - `secrets_feedback`: Which hidden secrets a guess gets feedback about.
+/// - `drift`: The largest step the secret number takes after every guess, chosen with
+///   `--drift`, or `None` if it stays put.
+/// - `drift_feedback`: Whether a guess is compared with where a moving secret was or with
+///   where it moved to, chosen with `--drift-feedback`, or `None` for the default
+///   `DriftFeedback`.
+/// - `seed`: The seed of a moving secret's start and steps, chosen with `--seed`, or `None`
+///   for a different game every time.
+/// - `open`: Whether the secret number has no maximum, chosen with `--open`. It is then at
+///   least `min`, and `max` is not used.
+/// - `group`: Whether the player asks if the secret number is in a set of numbers instead
+///   of guessing it, chosen with `--group`.
+/// - `questions`: How many yes-or-no questions the player may ask in twenty questions, chosen
+///   with `--twenty-questions` or `--questions`, or `None` for another game.
+/// - `batch`: How many guesses the player submits together in each turn, chosen with
+///   `--batch`, or `None` for one guess at a time.
+/// - `circle`: Whether the range wraps around like a clock face, chosen with `--circle`.
+/// - `delay`: When the feedback for a guess is shown, chosen with `--delay` or
+///   `--feedback-every`, or `None` to show it right away.
+/// - `streak`: Whether to play higher or lower, chosen with `--streak`.
+/// - `party`: The number of players of the party game, chosen with `--party`, or `None` for
+///   one player.
+/// - `rounds`: The number of rounds of the party game, chosen with `--rounds`, or `None` for
+///   `DEFAULT_ROUNDS`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub theme: Theme,
+    pub color: Option<bool>,
+    pub locale: Option<Locale>,
+    pub skip_wasted: bool,
+    pub min: BigInt,
+    pub max: BigInt,
+    pub decimals: Option<u32>,
+    pub tolerance: Option<Decimal>,
+    pub dimensions: Option<usize>,
+    pub rgb: bool,
+    pub feedback: Option<FeedbackMode>,
+    pub words: Option<WordList>,
+    pub years: Option<(i32, i32)>,
+    pub date_hints: bool,
+    pub secrets: Option<usize>,
+    pub secrets_feedback: SecretsFeedback,
+    pub drift: Option<u32>,
+    pub drift_feedback: Option<DriftFeedback>,
+    pub seed: Option<u64>,
+    pub open: bool,
+    pub group: bool,
+    pub questions: Option<u32>,
+    pub batch: Option<usize>,
+    pub circle: bool,
+    pub delay: Option<Delay>,
+    pub streak: bool,
+    pub party: Option<usize>,
+    pub rounds: Option<u32>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            theme: Theme::default(),
+            color: None,
+            locale: None,
+            skip_wasted: false,
+            min: BigInt::from(Guess::<u32>::MIN),
+            max: BigInt::from(Guess::<u32>::MAX),
+            decimals: None,
+            tolerance: None,
+            dimensions: None,
+            rgb: false,
+            feedback: None,
+            words: None,
+            years: None,
+            date_hints: false,
+            secrets: None,
+            secrets_feedback: SecretsFeedback::default(),
+            drift: None,
+            drift_feedback: None,
+            seed: None,
+            open: false,
+            group: false,
+            questions: None,
+            batch: None,
+            circle: false,
+            delay: None,
+            streak: false,
+            party: None,
+            rounds: None,
+        }
+    }
+}
+
+impl Options {
+    /// Parses the command-line arguments into `Options`.
+    ///
+    /// # Arguments
+    /// * `args` - The arguments to parse, without the program name.
+    ///
+    /// # Returns
+    /// Returns a `Result`:
+    /// - `Ok(Options)` if every argument was understood.
+    /// - `Err(String)` with a message describing the first argument that could not be used.
+    ///
+    /// # Supported arguments
+    /// - `--theme <name>`: Selects one of the themes in `Theme::NAMES`.
+    /// - `--color`: Forces colored output even when stdout is not a terminal.
+    /// - `--no-color`: Disables colored output.
+    /// - `--lang <tag>`: Selects the language, e.g. `en`, `es` or `fr`.
+    /// - `--skip-wasted`: Does not count wasted guesses. It only works with the classic game.
+    /// - `--min <n>`, `--max <n>`: Sets the smallest and largest possible secret number. Both
+    ///   may be negative or written as a power such as `10^100`, and `--min` must not be
+    ///   greater than `--max`.
+    /// - `--impossible`: Plays impossible mode, over the range 1 to 10^100.
+    /// - `--decimals <n>`: Makes the secret number a decimal with `n` decimal places, from 1
+    ///   to `decimal::MAX_PLACES`.
+    /// - `--tolerance <x>`: Makes every guess within `x` of the secret number correct. The
+    ///   secret number is then a decimal, with `decimal::REAL_PLACES` places unless
+    ///   `--decimals` says otherwise.
+    /// - `--dimensions <n>`: Makes the secret a point on a grid with `n` axes, 2 or 3, each
+    ///   over the range of `--min` and `--max`.
+    /// - `--rgb`: Makes the secret a color, with red, green and blue values from 0 to 255. It
+    ///   cannot be combined with `--dimensions`, `--min`, `--max` or `--impossible`.
+    /// - `--feedback <mode>`: Selects how a secret point or color is found, one of
+    ///   `FeedbackMode::NAMES`. It only works with `--dimensions` or `--rgb`.
+    /// - `--word`: Makes the secret a word from the built-in word list.
+    /// - `--dictionary <file>`: Makes the secret a word from a file with one word per line.
+    ///   Neither can be combined with `--min`, `--max` or `--impossible`.
+    /// - `--date`: Makes the secret a date from `DateGame::DEFAULT_YEARS`.
+    /// - `--years <first>-<last>`: Makes the secret a date from January 1 of the first year to
+    ///   December 31 of the last, e.g. `--years 1990-2020`. Neither `--date` nor `--years` can
+    ///   be combined with `--min`, `--max` or `--impossible`.
+    /// - `--date-hints`: Says whether the year or the month of a wrong date is right. It only
+    ///   works with `--date` or `--years`.
+    /// - `--secrets <k>`: Hides `k` different secret numbers at once, from 2 to
+    ///   `MAX_SECRETS`, which are found one at a time.
+    /// - `--nearest`: With several secrets, compares each guess only with the nearest hidden
+    ///   secret instead of all of them. It only works with `--secrets`.
+    /// - `--drift <d>`: Moves the secret number by a random step from `-d` to `d` after every
+    ///   guess, with `d` from 1 to `MAX_DRIFT`.
+    /// - `--drift-feedback <position>`: Selects which position of a moving secret a guess is
+    ///   compared with, `old` (the default) or `new`. It only works with `--drift`.
+    /// - `--seed <n>`: Makes a moving secret start and move the same way every game.
+    /// - `--open`: Lets the secret number be arbitrarily large, with `--min` as its only
+    ///   bound; it cannot be combined with `--max` or `--impossible`.
+    /// - `--group`: Plays group testing, where the player asks whether the secret number is
+    ///   in sets like `{3, 7, 10-20}`.
+    /// - `--twenty-questions`: Plays twenty questions, where the player asks yes-or-no
+    ///   questions like `> 40` or `even and not prime` and then names the number.
+    /// - `--questions <n>`: Plays twenty questions with `n` questions instead of 20.
+    /// - `--batch <k>`: Lets the player submit up to `k` guesses per turn, at least 2, and
+    ///   counts turns instead of guesses.
+    /// - `--circle`: Makes the range wrap around like a clock face, so the feedback is the
+    ///   shorter way round to the secret number, clockwise or counter-clockwise.
+    /// - `--delay <d>`: Shows the feedback for each guess only after `d` more guesses, with
+    ///   `d` from 1 to `MAX_DELAY`.
+    /// - `--feedback-every <n>`: Shows the feedback in batches, once every `n` guesses, with
+    ///   `n` from 2 to `MAX_DELAY`.
+    /// - `--streak`: Plays higher or lower, where the player calls whether each next number
+    ///   is higher or lower than the last one; the range needs at least 2 numbers.
+    /// - `--party <n>`: Plays the party game for `n` players, from 2 to `MAX_PLAYERS`, who each
+    ///   guess once per round without going over the secret number.
+    /// - `--rounds <r>`: Plays `r` rounds of the party game instead of `DEFAULT_ROUNDS`, with
+    ///   `r` from 1 to `MAX_ROUNDS`.
+    ///
+    /// Decimals, points, words, dates, several secrets, a moving secret, an open range, group
+    /// testing, twenty questions, batches, a circle, delayed feedback, higher or lower and the
+    /// party game are separate games, so only one of them can be chosen, and `--delay` cannot be
+    /// combined with `--feedback-every`.
+    pub fn parse<I, S>(args: I) -> Result<Options, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        // Whether the bounds were chosen, which some games do not allow.
+        let (mut min_given, mut max_given) = (false, false);
+
+        while let Some(arg) = args.next() {
+            match arg.as_ref() {
+                "--theme" => {
+                    let name = args.next().ok_or("--theme needs a value")?;
+                    options.theme = Theme::from_name(name.as_ref()).ok_or_else(|| {
+                        format!(
+                            "unknown theme '{}', expected one of: {}",
+                            name.as_ref(),
+                            Theme::NAMES.join(", ")
+                        )
+                    })?;
+                }
+                "--lang" => {
+                    let tag = args.next().ok_or("--lang needs a value")?;
+                    let locale = Locale::from_tag(tag.as_ref()).ok_or_else(|| {
+                        let codes: Vec<&str> = Locale::ALL.iter().map(Locale::code).collect();
+                        format!(
+                            "unsupported language '{}', expected one of: {}",
+                            tag.as_ref(),
+                            codes.join(", ")
+                        )
+                    })?;
+                    options.locale = Some(locale);
+                }
+                "--min" | "--max" => {
+                    let flag = arg.as_ref();
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("{} needs a value", flag))?;
+                    let value = parse_bound(flag, value.as_ref())?;
+                    if flag == "--min" {
+                        options.min = value;
+                        min_given = true;
+                    } else {
+                        options.max = value;
+                        max_given = true;
+                    }
+                }
+                "--impossible" => {
+                    (options.min, options.max) = Guess::impossible_range();
+                    (min_given, max_given) = (true, true);
+                }
+                "--decimals" => {
+                    let value = args.next().ok_or("--decimals needs a value")?;
+                    let places = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|places| (1..=decimal::MAX_PLACES).contains(places))
+                        .ok_or_else(|| {
+                            format!(
+                                "--decimals needs a number from 1 to {}, got '{}'",
+                                decimal::MAX_PLACES,
+                                value.as_ref()
+                            )
+                        })?;
+                    options.decimals = Some(places);
+                }
+                "--tolerance" => {
+                    let value = args.next().ok_or("--tolerance needs a value")?;
+                    let tolerance = Decimal::parse(value.as_ref().trim(), decimal::MAX_PLACES)
+                        .ok()
+                        .filter(|tolerance| tolerance.units() >= 0)
+                        .ok_or_else(|| {
+                            format!(
+                                "--tolerance needs a number of at least 0, got '{}'",
+                                value.as_ref()
+                            )
+                        })?;
+                    options.tolerance = Some(tolerance);
+                }
+                "--dimensions" => {
+                    let value = args.next().ok_or("--dimensions needs a value")?;
+                    let dimensions = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|dimensions| (2..=point::MAX_DIMENSIONS).contains(dimensions))
+                        .ok_or_else(|| {
+                            format!(
+                                "--dimensions needs a number from 2 to {}, got '{}'",
+                                point::MAX_DIMENSIONS,
+                                value.as_ref()
+                            )
+                        })?;
+                    options.dimensions = Some(dimensions);
+                }
+                "--rgb" => options.rgb = true,
+                "--word" => options.words = Some(WordList::Builtin),
+                "--date" => {
+                    options.years = options.years.or(Some(DateGame::DEFAULT_YEARS));
+                }
+                "--years" => {
+                    let value = args.next().ok_or("--years needs a value")?;
+                    options.years = Some(parse_years(value.as_ref()).ok_or_else(|| {
+                        format!(
+                            "--years needs two years from 1 to 9999 like 1990-2020, got '{}'",
+                            value.as_ref()
+                        )
+                    })?);
+                }
+                "--date-hints" => options.date_hints = true,
+                "--secrets" => {
+                    let value = args.next().ok_or("--secrets needs a value")?;
+                    let count = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|count| (2..=MAX_SECRETS).contains(count))
+                        .ok_or_else(|| {
+                            format!(
+                                "--secrets needs a number from 2 to {}, got '{}'",
+                                MAX_SECRETS,
+                                value.as_ref()
+                            )
+                        })?;
+                    options.secrets = Some(count);
+                }
+                "--nearest" => options.secrets_feedback = SecretsFeedback::Nearest,
+                "--drift" => {
+                    let value = args.next().ok_or("--drift needs a value")?;
+                    let drift = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|drift| (1..=MAX_DRIFT).contains(drift))
+                        .ok_or_else(|| {
+                            format!(
+                                "--drift needs a number from 1 to {}, got '{}'",
+                                MAX_DRIFT,
+                                value.as_ref()
+                            )
+                        })?;
+                    options.drift = Some(drift);
+                }
+                "--drift-feedback" => {
+                    let name = args.next().ok_or("--drift-feedback needs a value")?;
+                    let feedback = DriftFeedback::from_name(name.as_ref()).ok_or_else(|| {
+                        format!(
+                            "unknown drift feedback '{}', expected one of: {}",
+                            name.as_ref(),
+                            DriftFeedback::NAMES.join(", ")
+                        )
+                    })?;
+                    options.drift_feedback = Some(feedback);
+                }
+                "--seed" => {
+                    let value = args.next().ok_or("--seed needs a value")?;
+                    let seed = value.as_ref().parse().map_err(|_| {
+                        format!("--seed needs a whole number, got '{}'", value.as_ref())
+                    })?;
+                    options.seed = Some(seed);
+                }
+                "--open" => options.open = true,
+                "--group" => options.group = true,
+                "--circle" => options.circle = true,
+                "--streak" => options.streak = true,
+                "--party" => {
+                    let value = args.next().ok_or("--party needs a value")?;
+                    let players = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|players| (2..=MAX_PLAYERS).contains(players))
+                        .ok_or_else(|| {
+                            format!(
+                                "--party needs a number from 2 to {}, got '{}'",
+                                MAX_PLAYERS,
+                                value.as_ref()
+                            )
+                        })?;
+                    options.party = Some(players);
+                }
+                "--rounds" => {
+                    let value = args.next().ok_or("--rounds needs a value")?;
+                    let rounds = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|rounds| (1..=MAX_ROUNDS).contains(rounds))
+                        .ok_or_else(|| {
+                            format!(
+                                "--rounds needs a number from 1 to {}, got '{}'",
+                                MAX_ROUNDS,
+                                value.as_ref()
+                            )
+                        })?;
+                    options.rounds = Some(rounds);
+                }
+                "--delay" | "--feedback-every" => {
+                    let flag = arg.as_ref();
+                    if options.delay.is_some() {
+                        return Err("--delay and --feedback-every cannot be combined".to_string());
+                    }
+                    let least = if flag == "--delay" { 1 } else { 2 };
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("{} needs a value", flag))?;
+                    let guesses = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|guesses| (least..=MAX_DELAY).contains(guesses))
+                        .ok_or_else(|| {
+                            format!(
+                                "{} needs a number from {} to {}, got '{}'",
+                                flag,
+                                least,
+                                MAX_DELAY,
+                                value.as_ref()
+                            )
+                        })?;
+                    options.delay = Some(if flag == "--delay" {
+                        Delay::After(guesses)
+                    } else {
+                        Delay::Every(guesses)
+                    });
+                }
+                "--twenty-questions" => options.questions = Some(DEFAULT_QUESTIONS),
+                "--batch" => {
+                    let value = args.next().ok_or("--batch needs a value")?;
+                    let size = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|&size: &usize| size >= 2)
+                        .ok_or_else(|| {
+                            format!("--batch needs a number from 2 up, got '{}'", value.as_ref())
+                        })?;
+                    options.batch = Some(size);
+                }
+                "--questions" => {
+                    let value = args.next().ok_or("--questions needs a value")?;
+                    let questions = value
+                        .as_ref()
+                        .parse()
+                        .ok()
+                        .filter(|&questions: &u32| questions >= 1)
+                        .ok_or_else(|| {
+                            format!(
+                                "--questions needs a number from 1 up, got '{}'",
+                                value.as_ref()
+                            )
+                        })?;
+                    options.questions = Some(questions);
+                }
+                "--dictionary" => {
+                    let path = args.next().ok_or("--dictionary needs a value")?;
+                    options.words = Some(WordList::File(path.as_ref().into()));
+                }
+                "--feedback" => {
+                    let name = args.next().ok_or("--feedback needs a value")?;
+                    let mode = FeedbackMode::from_name(name.as_ref()).ok_or_else(|| {
+                        format!(
+                            "unknown feedback '{}', expected one of: {}",
+                            name.as_ref(),
+                            FeedbackMode::NAMES.join(", ")
+                        )
+                    })?;
+                    options.feedback = Some(mode);
+                }
+                "--color" => options.color = Some(true),
+                "--no-color" => options.color = Some(false),
+                "--skip-wasted" => options.skip_wasted = true,
+                other => return Err(format!("unknown argument '{}'", other)),
+            }
+        }
+
+        if options.open {
+            if max_given {
+                return Err(
+                    "--open has no maximum, so it cannot be combined with --max or \
+                     --impossible"
+                        .to_string(),
+                );
+            }
+        } else if options.min > options.max {
+            return Err(format!(
+                "--min ({}) must not be greater than --max ({})",
+                options.min, options.max
+            ));
+        }
+
+        let modes = [
+            options.decimal_places().is_some(),
+            options.dimensions.is_some() || options.rgb,
+            options.words.is_some(),
+            options.years.is_some(),
+            options.secrets.is_some(),
+            options.drift.is_some(),
+            options.open,
+            options.group,
+            options.questions.is_some(),
+            options.batch.is_some(),
+            options.circle,
+            options.delay.is_some(),
+            options.streak,
+            options.party.is_some(),
+        ];
+        if modes.iter().filter(|&&chosen| chosen).count() > 1 {
+            return Err(
+                "decimals, points, words, dates, several secrets, a moving secret, an open range, \
+                 group testing, twenty questions, batches, a circle, delayed feedback, higher or \
+                 lower and the party game cannot be combined"
+                    .to_string(),
+            );
+        }
+        if options.skip_wasted && modes.contains(&true) {
+            return Err("--skip-wasted only works with the classic game".to_string());
+        }
+        if options.secrets_feedback == SecretsFeedback::Nearest && options.secrets.is_none() {
+            return Err("--nearest only works with --secrets".to_string());
+        }
+        if options.seed.is_some() && options.drift.is_none() {
+            return Err("--seed only works with --drift".to_string());
+        }
+        if options.drift_feedback.is_some() && options.drift.is_none() {
+            return Err("--drift-feedback only works with --drift".to_string());
+        }
+        if options.date_hints && options.years.is_none() {
+            return Err("--date-hints only works with --date or --years".to_string());
+        }
+        if options.rounds.is_some() && options.party.is_none() {
+            return Err("--rounds only works with --party".to_string());
+        }
+        if options.feedback.is_some() && options.dimensions.is_none() && !options.rgb {
+            return Err("--feedback only works with --dimensions or --rgb".to_string());
+        }
+        if options.rgb && (options.dimensions.is_some() || min_given || max_given) {
+            return Err(
+                "--rgb always has 3 values from 0 to 255, so it cannot be combined with \
+                 --dimensions, --min, --max or --impossible"
+                    .to_string(),
+            );
+        }
+        if options.words.is_some() && (min_given || max_given) {
+            return Err(
+                "the secret word is not a number, so --word and --dictionary cannot be \
+                 combined with --min, --max or --impossible"
+                    .to_string(),
+            );
+        }
+        if options.years.is_some() && (min_given || max_given) {
+            return Err(
+                "the secret date is not a number, so --date and --years cannot be combined \
+                 with --min, --max or --impossible"
+                    .to_string(),
+            );
+        }
+        if let Some(count) = options.secrets {
+            if &options.max - &options.min + 1 < BigInt::from(count) {
+                return Err(format!(
+                    "the range {} to {} is too small for {} different secrets",
+                    options.min, options.max, count
+                ));
+            }
+        }
+        if options.streak && options.min == options.max {
+            return Err(format!(
+                "--streak needs at least 2 numbers, but the range is only {}",
+                options.min
+            ));
+        }
+        if options.questions.is_some()
+            && (options.min.to_i64().is_none() || options.max.to_i64().is_none())
+        {
+            return Err(format!(
+                "the range {} to {} is too large for twenty questions",
+                options.min, options.max
+            ));
+        }
+        if options.dimensions.is_some()
+            && (options.min.to_i64().is_none() || options.max.to_i64().is_none())
+        {
+            return Err(format!(
+                "the range {} to {} is too large for a point",
+                options.min, options.max
+            ));
+        }
+
+        if let Some(places) = options.decimal_places() {
+            if let Some(tolerance) = options.tolerance {
+                if tolerance.rescale(places).is_none() {
+                    return Err(format!(
+                        "--tolerance ({}) has more decimal places than --decimals ({})",
+                        tolerance.rescale(tolerance.scale()).unwrap_or(tolerance),
+                        places
+                    ));
+                }
+            }
+            let fits = |bound: &BigInt| {
+                bound
+                    .to_i64()
+                    .and_then(|bound| Decimal::from_integer(bound, places))
+                    .is_some()
+            };
+            if !fits(&options.min) || !fits(&options.max) {
+                return Err(format!(
+                    "the range {} to {} is too large for {} decimal places",
+                    options.min, options.max, places
+                ));
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Returns the number of decimal places of the secret number, or `None` if it is a whole
+    /// number.
+    ///
+    /// This is the number given with `--decimals`. With only `--tolerance`, it is
+    /// `decimal::REAL_PLACES`, or more if the tolerance itself needs more places.
+    pub fn decimal_places(&self) -> Option<u32> {
+        match (self.decimals, self.tolerance) {
+            (Some(places), _) => Some(places),
+            (None, Some(tolerance)) => Some(tolerance.scale().max(decimal::REAL_PLACES)),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the settings of the game with a secret point or color chosen with
+    /// `--dimensions` or `--rgb`, or `None` if the secret is a single number.
+    pub fn point_game(&self) -> Option<PointGame> {
+        let mode = self.feedback.unwrap_or_default();
+        if self.rgb {
+            return Some(PointGame::rgb(mode));
+        }
+        let dimensions = self.dimensions?;
+        let bound = |value: &BigInt| {
+            value
+                .to_i64()
+                .expect("Options::parse checks the range fits")
+        };
+        Some(PointGame::grid(
+            dimensions,
+            bound(&self.min),
+            bound(&self.max),
+            mode,
+        ))
+    }
+
+    /// Returns the settings of the game with a secret date chosen with `--date` or `--years`,
+    /// or `None` if the secret is not a date.
+    ///
+    /// # Arguments
+    /// * `month_first` - Whether numeric dates put the month first, which depends on the
+    ///   language (see `Locale::month_first`).
+    pub fn date_game(&self, month_first: bool) -> Option<DateGame> {
+        let (first, last) = self.years?;
+        Some(DateGame::new(first, last, self.date_hints, month_first))
+    }
+
+    /// Returns the settings of twenty questions chosen with `--twenty-questions` or
+    /// `--questions`, or `None` if another game was chosen.
+    pub fn question_game(&self) -> Option<QuestionGame> {
+        let questions = self.questions?;
+        let bound = |value: &BigInt| {
+            value
+                .to_i64()
+                .expect("Options::parse checks the range fits")
+        };
+        Some(QuestionGame::new(
+            bound(&self.min),
+            bound(&self.max),
+            questions,
+        ))
+    }
+
+    /// Returns the settings of the decimal game chosen with `--decimals` or `--tolerance`,
+    /// over the range of `--min` and `--max`, or `None` if the secret number is a whole
+    /// number.
+    pub fn decimal_game(&self) -> Option<DecimalGame> {
+        let places = self.decimal_places()?;
+        let bound = |value: &BigInt| {
+            value
+                .to_i64()
+                .and_then(|value| Decimal::from_integer(value, places))
+                .expect("Options::parse checks the range fits")
+        };
+        let tolerance = self
+            .tolerance
+            .and_then(|tolerance| tolerance.rescale(places))
+            .unwrap_or_else(|| Decimal::new(0, places));
+        Some(DecimalGame::new(
+            bound(&self.min),
+            bound(&self.max),
+            tolerance,
+        ))
+    }
+
+    /// Returns the theme to use, with colors switched on or off.
+    ///
+    /// An explicit `--color` or `--no-color` wins; otherwise `detected` decides, which is
+    /// normally the result of `theme::colors_supported`.
+    pub fn resolved_theme(&self, detected: bool) -> Theme {
+        let enabled = self.color.unwrap_or(detected);
+        self.theme.clone().with_colors(enabled)
+    }
+
+    /// Returns the locale to use: the one chosen with `--lang`, or else the one from the
+    /// environment.
+    pub fn resolved_locale(&self) -> Locale {
+        self.locale.unwrap_or_else(Locale::from_env)
+    }
+}
+
+/// The largest number of bits a bound of the range can have, so a bound like `7^4000000000`
+/// is refused instead of taking forever to compute.
+pub const MAX_BOUND_BITS: u64 = 4096;
+
+/// Parses a bound of the range given with `flag`: a whole number such as `-500`, or a power
+/// such as `10^100`, of at most `MAX_BOUND_BITS` bits.
+fn parse_bound(flag: &str, text: &str) -> Result<BigInt, String> {
+    let not_a_number = || format!("{} needs a whole number, got '{}'", flag, text);
+    let too_large = || {
+        format!(
+            "{} must fit in {} bits, got '{}'",
+            flag, MAX_BOUND_BITS, text
+        )
+    };
+    let value: BigInt = match text.split_once('^') {
+        Some((base, exponent)) => {
+            let base: BigInt = base.trim().parse().map_err(|_| not_a_number())?;
+            let exponent: u32 = exponent.trim().parse().map_err(|_| not_a_number())?;
+            // The power has at least `exponent` times the bits of the base after its first,
+            // so a power that is sure to be too large is refused before it is computed.
+            if (base.bits().saturating_sub(1)).saturating_mul(u64::from(exponent)) > MAX_BOUND_BITS
+            {
+                return Err(too_large());
+            }
+            base.pow(exponent)
+        }
+        None => text.trim().parse().map_err(|_| not_a_number())?,
+    };
+    if value.bits() > MAX_BOUND_BITS {
+        return Err(too_large());
+    }
+    Ok(value)
+}
+
+/// Parses a range of years such as `1990-2020`, which must be in order and from 1 to 9999.
+fn parse_years(text: &str) -> Option<(i32, i32)> {
+    let (first, last) = text.split_once('-')?;
+    let (first, last): (i32, i32) = (first.trim().parse().ok()?, last.trim().parse().ok()?);
+    let valid = |year| Date::new(year, 1, 1).is_some();
+    (valid(first) && valid(last) && first <= last).then_some((first, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that no arguments gives the default options
+    #[test]
+    fn parse_no_arguments() {
+        let options = Options::parse(Vec::<String>::new()).unwrap();
+        assert_eq!(options, Options::default());
+    }
+
+    // Test selecting a theme and disabling colors
+    #[test]
+    fn parse_theme_and_color() {
+        let options = Options::parse(["--theme", "high-contrast", "--no-color"]).unwrap();
+        assert_eq!(options.theme, Theme::high_contrast());
+        assert_eq!(options.color, Some(false));
+        assert_eq!(
+            options.resolved_theme(true),
+            Theme::high_contrast().with_colors(false)
+        );
+    }
+
+    // Test selecting a language
+    #[test]
+    fn parse_lang() {
+        let options = Options::parse(["--lang", "fr_FR"]).unwrap();
+        assert_eq!(options.locale, Some(Locale::French));
+        assert_eq!(options.resolved_locale(), Locale::French);
+        assert!(Options::parse(["--lang", "xx"]).is_err());
+    }
+
+    // Test that wasted guesses are counted unless asked otherwise
+    #[test]
+    fn parse_skip_wasted() {
+        assert!(!Options::default().skip_wasted);
+        assert!(Options::parse(["--skip-wasted"]).unwrap().skip_wasted);
+        assert!(Options::parse(["--skip-wasted", "--max", "1000"]).is_ok());
+        assert!(Options::parse(["--skip-wasted", "--circle"]).is_err());
+        assert!(Options::parse(["--skip-wasted", "--word"]).is_err());
+    }
+
+    // Test choosing the range, including negative numbers
+    #[test]
+    fn parse_range() {
+        let options = Options::parse(["--min", "-500", "--max", "500"]).unwrap();
+        assert_eq!(
+            (options.min, options.max),
+            (BigInt::from(-500), BigInt::from(500))
+        );
+        let options = Options::parse(["--max", "10^15"]).unwrap();
+        assert_eq!(
+            (options.min, options.max),
+            (BigInt::from(1), BigInt::from(1_000_000_000_000_000u64))
+        );
+        let options = Options::parse(["--impossible"]).unwrap();
+        assert_eq!((options.min, options.max), Guess::impossible_range());
+        assert!(Options::parse(["--min", "50", "--max", "10"]).is_err());
+        assert!(Options::parse(["--min", "ten"]).is_err());
+        assert!(Options::parse(["--max"]).is_err());
+    }
+
+    // Test choosing a decimal secret number and a tolerance
+    #[test]
+    fn parse_decimals_and_tolerance() {
+        assert_eq!(Options::default().decimal_game(), None);
+
+        let game = Options::parse(["--decimals", "2", "--min", "0", "--max", "10"])
+            .unwrap()
+            .decimal_game()
+            .unwrap();
+        assert_eq!(game.range(), (Decimal::new(0, 2), Decimal::new(1000, 2)));
+        assert_eq!(game.tolerance(), Decimal::new(0, 2));
+
+        let game = Options::parse(["--tolerance", "0.05"])
+            .unwrap()
+            .decimal_game()
+            .unwrap();
+        assert_eq!(game.places(), decimal::REAL_PLACES);
+        assert_eq!(game.tolerance(), Decimal::new(50_000, 6));
+
+        let game = Options::parse(["--tolerance", "0,5", "--decimals", "1"])
+            .unwrap()
+            .decimal_game()
+            .unwrap();
+        assert_eq!(game.tolerance(), Decimal::new(5, 1));
+
+        assert!(Options::parse(["--decimals", "0"]).is_err());
+        assert!(Options::parse(["--decimals", "10"]).is_err());
+        assert!(Options::parse(["--tolerance", "-1"]).is_err());
+        assert!(Options::parse(["--tolerance", "0.05", "--decimals", "1"]).is_err());
+        assert!(Options::parse(["--decimals", "9", "--max", "10^12"]).is_err());
+    }
+
+    // Test choosing a secret point or color
+    #[test]
+    fn parse_points() {
+        assert_eq!(Options::default().point_game(), None);
+
+        let game = Options::parse(["--dimensions", "3", "--feedback", "distance"])
+            .unwrap()
+            .point_game()
+            .unwrap();
+        assert_eq!(game, PointGame::grid(3, 1, 100, FeedbackMode::Distance));
+
+        let game = Options::parse(["--rgb"]).unwrap().point_game().unwrap();
+        assert!(game.is_rgb());
+        assert_eq!(game.range(), (0, 255));
+
+        assert!(Options::parse(["--dimensions", "1"]).is_err());
+        assert!(Options::parse(["--dimensions", "4"]).is_err());
+        assert!(Options::parse(["--feedback", "psychic"]).is_err());
+        assert!(Options::parse(["--rgb", "--decimals", "2"]).is_err());
+        assert!(Options::parse(["--dimensions", "2", "--impossible"]).is_err());
+        assert!(Options::parse(["--feedback", "distance"]).is_err());
+        assert!(Options::parse(["--rgb", "--feedback", "distance"]).is_ok());
+        assert!(Options::parse(["--rgb", "--dimensions", "2"]).is_err());
+        assert!(Options::parse(["--rgb", "--min", "0"]).is_err());
+        assert!(Options::parse(["--max", "255", "--rgb"]).is_err());
+        assert!(Options::parse(["--rgb", "--impossible"]).is_err());
+    }
+
+    // Test choosing word mode
+    #[test]
+    fn parse_words() {
+        assert_eq!(Options::default().words, None);
+        assert_eq!(
+            Options::parse(["--word"]).unwrap().words,
+            Some(WordList::Builtin)
+        );
+        assert_eq!(
+            Options::parse(["--dictionary", "words.txt"]).unwrap().words,
+            Some(WordList::File("words.txt".into()))
+        );
+        assert!(Options::parse(["--dictionary"]).is_err());
+        assert!(Options::parse(["--word", "--rgb"]).is_err());
+        assert!(Options::parse(["--word", "--min", "5"]).is_err());
+        assert!(Options::parse(["--max", "100", "--word"]).is_err());
+        assert!(Options::parse(["--dictionary", "words.txt", "--impossible"]).is_err());
+    }
+
+    // Test choosing date mode
+    #[test]
+    fn parse_dates() {
+        assert_eq!(Options::default().date_game(false), None);
+        let options = Options::parse(["--date"]).unwrap();
+        assert_eq!(options.years, Some(DateGame::DEFAULT_YEARS));
+        let options = Options::parse(["--years", "1990-2020", "--date-hints", "--date"]).unwrap();
+        assert_eq!(options.years, Some((1990, 2020)));
+        assert_eq!(
+            options.date_game(true),
+            Some(DateGame::new(1990, 2020, true, true))
+        );
+        assert!(Options::parse(["--years", "2020-1990"]).is_err());
+        assert!(Options::parse(["--years", "0-10"]).is_err());
+        assert!(Options::parse(["--years", "1990"]).is_err());
+        assert!(Options::parse(["--date", "--word"]).is_err());
+        assert!(Options::parse(["--date-hints"]).is_err());
+        assert!(Options::parse(["--date", "--min", "5"]).is_err());
+        assert!(Options::parse(["--years", "1990-2020", "--max", "2000"]).is_err());
+        assert!(Options::parse(["--impossible", "--date"]).is_err());
+    }
+
+    // Test hiding several secrets
+    #[test]
+    fn parse_secrets() {
+        assert_eq!(Options::default().secrets, None);
+        let options = Options::parse(["--secrets", "3", "--nearest"]).unwrap();
+        assert_eq!(options.secrets, Some(3));
+        assert_eq!(options.secrets_feedback, SecretsFeedback::Nearest);
+        assert!(Options::parse(["--secrets", "1"]).is_err());
+        assert!(Options::parse(["--secrets", "101", "--max", "1000"]).is_err());
+        assert!(Options::parse(["--secrets", "18446744073709551615", "--max", "10^30"]).is_err());
+        assert!(Options::parse(["--secrets", "5", "--min", "1", "--max", "4"]).is_err());
+        assert!(Options::parse(["--secrets", "2", "--date"]).is_err());
+        assert!(Options::parse(["--nearest"]).is_err());
+    }
+
+    // Test choosing a moving secret
+    #[test]
+    fn parse_drift() {
+        assert_eq!(Options::default().drift, None);
+        let options =
+            Options::parse(["--drift", "3", "--drift-feedback", "new", "--seed", "42"]).unwrap();
+        assert_eq!(options.drift, Some(3));
+        assert_eq!(options.drift_feedback, Some(DriftFeedback::New));
+        assert_eq!(options.seed, Some(42));
+        assert!(Options::parse(["--drift", "0"]).is_err());
+        assert!(Options::parse(["--drift", "2", "--drift-feedback", "later"]).is_err());
+        assert!(Options::parse(["--seed", "42"]).is_err());
+        assert!(Options::parse(["--drift-feedback", "new"]).is_err());
+        assert!(Options::parse(["--drift-feedback", "old"]).is_err());
+        assert!(Options::parse(["--drift", "2", "--secrets", "2"]).is_err());
+    }
+
+    // Test choosing an open range
+    #[test]
+    fn parse_open() {
+        assert!(!Options::default().open);
+        let options = Options::parse(["--open", "--min", "500"]).unwrap();
+        assert!(options.open);
+        assert_eq!(options.min, BigInt::from(500));
+        assert!(Options::parse(["--open", "--max", "1000"]).is_err());
+        assert!(Options::parse(["--open", "--max", "100"]).is_err());
+        assert!(Options::parse(["--impossible", "--open"]).is_err());
+        assert!(Options::parse(["--open", "--drift", "2"]).is_err());
+    }
+
+    // Test choosing group testing
+    #[test]
+    fn parse_group() {
+        assert!(!Options::default().group);
+        assert!(Options::parse(["--group", "--max", "1000"]).unwrap().group);
+        assert!(Options::parse(["--group", "--open"]).is_err());
+    }
+
+    // Test choosing twenty questions
+    #[test]
+    fn parse_questions() {
+        assert_eq!(Options::default().question_game(), None);
+        let options = Options::parse(["--twenty-questions"]).unwrap();
+        assert_eq!(options.question_game(), Some(QuestionGame::new(1, 100, 20)));
+        let options = Options::parse(["--questions", "5", "--max", "10"]).unwrap();
+        assert_eq!(options.question_game(), Some(QuestionGame::new(1, 10, 5)));
+        assert!(Options::parse(["--questions", "0"]).is_err());
+        assert!(Options::parse(["--twenty-questions", "--impossible"]).is_err());
+        assert!(Options::parse(["--twenty-questions", "--group"]).is_err());
+    }
+
+    // Test choosing batches of guesses
+    #[test]
+    fn parse_batch() {
+        assert_eq!(Options::default().batch, None);
+        assert_eq!(Options::parse(["--batch", "3"]).unwrap().batch, Some(3));
+        assert!(Options::parse(["--batch", "1"]).is_err());
+        assert!(Options::parse(["--batch", "3", "--group"]).is_err());
+    }
+
+    // Test choosing a circular range
+    #[test]
+    fn parse_circle() {
+        assert!(!Options::default().circle);
+        let options = Options::parse(["--circle", "--min", "0", "--max", "59"]).unwrap();
+        assert!(options.circle);
+        assert_eq!(
+            (options.min, options.max),
+            (BigInt::from(0), BigInt::from(59))
+        );
+        assert!(Options::parse(["--circle", "--batch", "2"]).is_err());
+    }
+
+    // Test choosing delayed feedback
+    #[test]
+    fn parse_delay() {
+        assert_eq!(Options::default().delay, None);
+        assert_eq!(
+            Options::parse(["--delay", "2"]).unwrap().delay,
+            Some(Delay::After(2))
+        );
+        assert_eq!(
+            Options::parse(["--feedback-every", "5"]).unwrap().delay,
+            Some(Delay::Every(5))
+        );
+        assert!(Options::parse(["--delay", "0"]).is_err());
+        assert!(Options::parse(["--feedback-every", "1"]).is_err());
+        assert!(Options::parse(["--delay", "1", "--feedback-every", "3"]).is_err());
+        assert!(Options::parse(["--delay", "1", "--circle"]).is_err());
+    }
+
+    // Test choosing higher or lower
+    #[test]
+    fn parse_streak() {
+        assert!(!Options::default().streak);
+        assert!(Options::parse(["--streak", "--max", "10"]).unwrap().streak);
+        assert!(Options::parse(["--streak", "--min", "5", "--max", "5"]).is_err());
+        assert!(Options::parse(["--streak", "--circle"]).is_err());
+    }
+
+    // Test choosing the party game
+    #[test]
+    fn parse_party() {
+        assert_eq!(Options::default().party, None);
+        let options = Options::parse(["--party", "3", "--rounds", "2"]).unwrap();
+        assert_eq!((options.party, options.rounds), (Some(3), Some(2)));
+        assert_eq!(Options::parse(["--party", "4"]).unwrap().rounds, None);
+        assert!(Options::parse(["--party", "1"]).is_err());
+        assert!(Options::parse(["--party", "11"]).is_err());
+        assert!(Options::parse(["--party", "2", "--rounds", "0"]).is_err());
+        assert!(Options::parse(["--party", "2", "--rounds", "100"]).is_ok());
+        assert!(Options::parse(["--party", "2", "--rounds", "101"]).is_err());
+        assert!(Options::parse(["--party", "2", "--rounds", "4294967295"]).is_err());
+        assert!(Options::parse(["--rounds", "3"]).is_err());
+        assert!(Options::parse(["--party", "2", "--streak"]).is_err());
+    }
+
+    // Test that bad arguments are reported
+    #[test]
+    fn parse_errors() {
+        assert!(Options::parse(["--theme"]).is_err());
+        assert!(Options::parse(["--theme", "neon"]).is_err());
+        assert!(Options::parse(["--bogus"]).is_err());
+        assert!(Options::parse(["--max", "7^4000000000"]).is_err());
+        assert!(Options::parse(["--max", "2^4097"]).is_err());
+        assert!(Options::parse(["--max", "2^4095"]).is_ok());
+        assert!(Options::parse(["--min", "1^4000000000"]).is_ok());
+    }
+}
@@ -0,0 +1,346 @@
+use crate::{
+    command::Command, get_secret_number, integer::Integer, parse_player_input, prompt::Prompter,
+    theme::Role, GameOutcome, Guess, GuessResult, Guessable, PlayerInput,
+};
+use std::io::{BufRead, Write};
+
+/// The largest number of players in a party.
+pub const MAX_PLAYERS: usize = 10;
+
+/// The number of rounds of a party unless chosen otherwise.
+pub const DEFAULT_ROUNDS: u32 = 5;
+
+/// The largest number of rounds of a party.
+pub const MAX_ROUNDS: u32 = 100;
+
+/// The points for the closest guess of a round that does not go over the secret.
+pub const CLOSEST_POINTS: u32 = 1;
+
+/// The points for guessing the secret exactly, instead of `CLOSEST_POINTS`.
+pub const EXACT_POINTS: u32 = 2;
+
+/// How one player's guess did in a round of the party game.
+///
+/// # Fields
+/// - `player`: The index of the player, in the order the guesses were given.
+/// - `rank`: The place of the guess among those that did not go over the secret, starting at
+///   1 for the closest, or `None` if it went over. Equally close guesses share a place.
+/// - `exact`: Whether the guess was the secret.
+/// - `points`: The points the guess scored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placing {
+    pub player: usize,
+    pub rank: Option<usize>,
+    pub exact: bool,
+    pub points: u32,
+}
+
+/// Ranks the guesses of one round by how close they came to the secret without going over,
+/// Price-is-Right style.
+///
+/// The guesses are compared only with `Guessable::compare`: a guess that is too big for the
+/// secret went over, and among the others, a greater guess is a closer one. The closest
+/// guesses score `CLOSEST_POINTS` each, or `EXACT_POINTS` if they are the secret;
+/// when every guess went over, nobody scores.
+///
+/// # Arguments
+/// * `guesses` - The guess of each player, in order.
+/// * `secret` - The secret of the round.
+///
+/// # Returns
+/// Returns the `Placing` of each guess, in the same order as `guesses`.
+pub fn rank_guesses<G>(guesses: &[G], secret: &G) -> Vec<Placing>
+where
+    G: Guessable<Feedback = GuessResult>,
+{
+    let under: Vec<bool> = guesses
+        .iter()
+        .map(|guess| guess.compare(secret) != GuessResult::TooBig)
+        .collect();
+    guesses
+        .iter()
+        .enumerate()
+        .map(|(player, guess)| {
+            if !under[player] {
+                return Placing {
+                    player,
+                    rank: None,
+                    exact: false,
+                    points: 0,
+                };
+            }
+            let closer = guesses
+                .iter()
+                .zip(&under)
+                .filter(|(other, &under)| under && other.compare(guess) == GuessResult::TooBig)
+                .count();
+            let rank = closer + 1;
+            let exact = guess.compare(secret) == GuessResult::Correct;
+            let points = match (rank, exact) {
+                (_, true) => EXACT_POINTS,
+                (1, false) => CLOSEST_POINTS,
+                _ => 0,
+            };
+            Placing {
+                player,
+                rank: Some(rank),
+                exact,
+                points,
+            }
+        })
+        .collect()
+}
+
+/// A struct keeping the point totals of the players over the rounds of a party.
+///
+/// # Fields
+/// - `points`: The total points of each player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scoreboard {
+    points: Vec<u32>,
+}
+
+impl Scoreboard {
+    /// Creates a scoreboard for `players` players, all with no points.
+    pub fn new(players: usize) -> Scoreboard {
+        Scoreboard {
+            points: vec![0; players],
+        }
+    }
+
+    /// Adds the points of a round.
+    pub fn add(&mut self, placings: &[Placing]) {
+        for placing in placings {
+            self.points[placing.player] += placing.points;
+        }
+    }
+
+    /// Returns the total points of each player.
+    pub fn points(&self) -> &[u32] {
+        &self.points
+    }
+
+    /// Returns the players with the most points, and how many points they have.
+    pub fn leaders(&self) -> (Vec<usize>, u32) {
+        let best = self.points.iter().copied().max().unwrap_or(0);
+        let leaders = (0..self.points.len())
+            .filter(|&player| self.points[player] == best)
+            .collect();
+        (leaders, best)
+    }
+}
+
+/// Plays one party game and returns how it ended.
+///
+/// The game has `rounds` rounds with a new secret number each. In every round, each player
+/// types exactly one guess, hidden from the others, and once all are in, the secret number and
+/// the guesses are revealed. The guesses are ranked by `rank_guesses`: the closest one
+/// that does not go over the secret number scores, more if it is exact. The `Scoreboard` keeps
+/// the totals, shown after every round, and the players with the most points after the last
+/// round win. Only the `:q` command is available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `players` - The number of players.
+/// * `rounds` - The number of rounds.
+/// * `min`, `max` - The smallest and largest possible secret number.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    players: usize,
+    rounds: u32,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    prompter.say(&ui.plural(
+        Role::Info,
+        "party-intro",
+        u64::from(rounds),
+        &[("players", &players)],
+    ));
+
+    let names: Vec<String> = (1..=players)
+        .map(|number| ui.catalog.format("party-player", &[("number", &number)]))
+        .collect();
+    let mut scoreboard = Scoreboard::new(players);
+    prompter.set_hidden(true);
+
+    let mut outcome = GameOutcome::Won;
+    'rounds: for round in 1..=rounds {
+        prompter.say(&ui.format(
+            Role::Headline,
+            "party-round",
+            &[("round", &round), ("rounds", &rounds)],
+        ));
+        let secret = get_secret_number(min.clone(), max.clone());
+        let secret_number = Guess::in_range(secret.clone(), min.clone(), max.clone())
+            .expect("Failed to generate secret number");
+
+        let mut guesses = Vec::with_capacity(players);
+        for name in &names {
+            prompter.say(&ui.format(Role::Info, "party-turn", &[("player", name)]));
+            let guess = loop {
+                let input =
+                    prompter.next(|line| parse_player_input(line, min.clone(), max.clone(), None));
+                match input {
+                    Ok(PlayerInput::Guess(guess)) => break Ok(guess),
+                    Ok(PlayerInput::Command(Command::Quit)) => break Err(GameOutcome::Quit),
+                    Ok(PlayerInput::Command(_)) => {
+                        prompter.say(&ui.text(Role::Info, "command-unavailable"))
+                    }
+                    Err(outcome) => break Err(outcome),
+                }
+            };
+            match guess {
+                Ok(guess) => guesses.push(guess),
+                Err(end) => {
+                    prompter.say(&ui.format(
+                        Role::Headline,
+                        "party-secret",
+                        &[("secret", &secret)],
+                    ));
+                    outcome = end;
+                    break 'rounds;
+                }
+            }
+        }
+
+        prompter.say(&ui.format(Role::Headline, "party-secret", &[("secret", &secret)]));
+        let placings = rank_guesses(&guesses, &secret_number);
+        for (placing, guess) in placings.iter().zip(&guesses) {
+            let player = &names[placing.player];
+            let value = guess.value();
+            let message = match (placing.exact, placing.rank) {
+                (true, _) => ui.format(
+                    Role::Correct,
+                    "party-exact",
+                    &[("player", player), ("value", &value)],
+                ),
+                (false, Some(_)) => {
+                    let distance = T::distance(&value, &secret);
+                    ui.format(
+                        Role::TooSmall,
+                        "party-under",
+                        &[
+                            ("player", player),
+                            ("value", &value),
+                            ("distance", &distance),
+                        ],
+                    )
+                }
+                (false, None) => ui.format(
+                    Role::TooBig,
+                    "party-over",
+                    &[("player", player), ("value", &value)],
+                ),
+            };
+            prompter.say(&message);
+        }
+
+        let scorers: Vec<&Placing> = placings
+            .iter()
+            .filter(|placing| placing.points > 0)
+            .collect();
+        if scorers.is_empty() {
+            prompter.say(&ui.text(Role::Info, "party-nobody"));
+        }
+        for placing in scorers {
+            prompter.say(&ui.plural(
+                Role::Correct,
+                "party-points",
+                u64::from(placing.points),
+                &[("player", &names[placing.player])],
+            ));
+        }
+
+        scoreboard.add(&placings);
+        let scores: Vec<String> = names
+            .iter()
+            .zip(scoreboard.points())
+            .map(|(player, points)| {
+                ui.catalog
+                    .format("party-score", &[("player", player), ("points", points)])
+            })
+            .collect();
+        prompter.say(&ui.format(
+            Role::Info,
+            "party-totals",
+            &[("scores", &scores.join(", "))],
+        ));
+    }
+
+    if outcome == GameOutcome::Won {
+        let (leaders, points) = scoreboard.leaders();
+        let leaders: Vec<&str> = leaders
+            .iter()
+            .map(|&player| names[player].as_str())
+            .collect();
+        let message = match leaders.as_slice() {
+            [player] => ui.plural(
+                Role::Correct,
+                "party-winner",
+                u64::from(points),
+                &[("player", player)],
+            ),
+            _ => ui.plural(
+                Role::Correct,
+                "party-tie",
+                u64::from(points),
+                &[("players", &leaders.join(", "))],
+            ),
+        };
+        prompter.say(&message);
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Guess;
+
+    fn guesses(values: &[i64]) -> Vec<Guess<i64>> {
+        values
+            .iter()
+            .map(|&value| Guess::in_range(value, 1, 100).unwrap())
+            .collect()
+    }
+
+    fn points(placings: &[Placing]) -> Vec<u32> {
+        placings.iter().map(|placing| placing.points).collect()
+    }
+
+    // Test ranking guesses without going over
+    #[test]
+    fn closest_without_going_over() {
+        let secret = Guess::in_range(50, 1, 100).unwrap();
+        let placings = rank_guesses(&guesses(&[40, 49, 51, 20]), &secret);
+        let ranks: Vec<Option<usize>> = placings.iter().map(|placing| placing.rank).collect();
+        assert_eq!(ranks, [Some(2), Some(1), None, Some(3)]);
+        assert_eq!(points(&placings), [0, CLOSEST_POINTS, 0, 0]);
+
+        let placings = rank_guesses(&guesses(&[50, 45, 50]), &secret);
+        assert_eq!(points(&placings), [EXACT_POINTS, 0, EXACT_POINTS]);
+        assert!(placings[0].exact);
+
+        let placings = rank_guesses(&guesses(&[60, 70]), &secret);
+        assert_eq!(points(&placings), [0, 0]);
+
+        let placings = rank_guesses(&guesses(&[30, 30]), &secret);
+        assert_eq!(points(&placings), [CLOSEST_POINTS, CLOSEST_POINTS]);
+    }
+
+    // Test adding up points over several rounds
+    #[test]
+    fn point_totals() {
+        let secret = Guess::in_range(50, 1, 100).unwrap();
+        let mut scoreboard = Scoreboard::new(3);
+        scoreboard.add(&rank_guesses(&guesses(&[40, 50, 60]), &secret));
+        scoreboard.add(&rank_guesses(&guesses(&[45, 10, 99]), &secret));
+        assert_eq!(scoreboard.points(), [1, 2, 0]);
+        assert_eq!(scoreboard.leaders(), (vec![1], 2));
+        scoreboard.add(&rank_guesses(&guesses(&[49, 1, 1]), &secret));
+        assert_eq!(scoreboard.leaders(), (vec![0, 1], 2));
+    }
+}
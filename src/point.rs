@@ -0,0 +1,677 @@
+use crate::{
+    command::Command, get_secret_number, handle_guess, integer::Integer, prompt::Prompter,
+    theme::Role, ui::Ui, GameOutcome, Guess, GuessCount, GuessError, GuessResult, Guessable,
+    Incrementable, Parsable,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::{BufRead, Write},
+};
+
+/// The largest number of coordinates a point can have.
+pub const MAX_DIMENSIONS: usize = 3;
+
+/// A point on a grid, such as `(12, 40)`, or a color such as `(255, 128, 0)`.
+///
+/// # Fields
+/// - `coords`: The coordinates of the point, one per axis.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Point {
+    coords: Vec<i64>,
+}
+
+impl Point {
+    /// Creates a point with the given coordinates.
+    pub fn new(coords: Vec<i64>) -> Point {
+        Point { coords }
+    }
+
+    /// Returns the coordinates of the point.
+    pub fn coords(&self) -> &[i64] {
+        &self.coords
+    }
+
+    /// Returns the straight-line (Euclidean) distance to `other`.
+    pub fn distance(&self, other: &Point) -> f64 {
+        self.coords
+            .iter()
+            .zip(&other.coords)
+            .map(|(a, b)| (*a as f64 - *b as f64).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coords: Vec<String> = self.coords.iter().map(i64::to_string).collect();
+        write!(f, "({})", coords.join(", "))
+    }
+}
+
+/// What comparing a guessed point with the secret point tells the player.
+///
+/// # Fields
+/// - `axes`: The result for each coordinate, telling whether it is too small, too big or
+///   correct.
+/// - `distance`: The straight-line distance from the guess to the secret point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointComparison {
+    pub axes: Vec<GuessResult>,
+    pub distance: f64,
+}
+
+impl PointComparison {
+    /// Returns whether the guess is the secret point, with every coordinate correct.
+    pub fn is_correct(&self) -> bool {
+        self.axes
+            .iter()
+            .all(|result| *result == GuessResult::Correct)
+    }
+}
+
+/// Trait implementation for `Point` to make it "guessable".
+///
+/// Points have no order, so a single `GuessResult` cannot say where the secret point lies.
+/// Instead, one comparison gives the result for each axis and the distance to the secret
+/// point, and `PointGame::feedback` reports the part its `FeedbackMode` asks for.
+impl Guessable for Point {
+    type Feedback = PointComparison;
+
+    fn compare(&self, other: &Self) -> PointComparison {
+        PointComparison {
+            axes: self
+                .coords
+                .iter()
+                .zip(&other.coords)
+                .map(|(guess, secret)| guess.cmp(secret).into())
+                .collect(),
+            distance: self.distance(other),
+        }
+    }
+}
+
+/// Trait implementation for `Point` to make it parsable from a string input.
+///
+/// The input is parsed by `PointGame::parse` for the default game, a 2D grid with both
+/// coordinates from 1 to 100.
+impl Parsable for Point {
+    type Value = i64;
+
+    fn parse_input(input: &str) -> Result<Point, GuessError<i64>> {
+        PointGame::default().parse(input)
+    }
+}
+
+/// Enum to represent how the player is told where the secret point is.
+///
+/// # Variants
+///
+/// - `Axes`: Each coordinate is reported as too small, too big or correct.
+/// - `Distance`: Only the distance is reported, as hot or cold, and whether the guess was
+///   closer than the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FeedbackMode {
+    #[default]
+    Axes,
+    Distance,
+}
+
+impl FeedbackMode {
+    /// The names the feedback modes can be chosen by.
+    pub const NAMES: [&'static str; 2] = ["axes", "distance"];
+
+    /// Looks up a feedback mode by name, returning `None` if there is none by that name.
+    pub fn from_name(name: &str) -> Option<FeedbackMode> {
+        match name {
+            "axes" => Some(FeedbackMode::Axes),
+            "distance" => Some(FeedbackMode::Distance),
+            _ => None,
+        }
+    }
+}
+
+/// Enum to represent how close a guess is to the secret point, relative to the size of the
+/// grid.
+///
+/// # Variants
+///
+/// - `Burning`: Within 5% of the longest possible distance.
+/// - `Hot`: Within 15%.
+/// - `Warm`: Within 30%.
+/// - `Cool`: Within 50%.
+/// - `Cold`: Further away.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Temperature {
+    Burning,
+    Hot,
+    Warm,
+    Cool,
+    Cold,
+}
+
+impl Temperature {
+    /// Returns the catalog key of the message for this temperature.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Temperature::Burning => "temperature-burning",
+            Temperature::Hot => "temperature-hot",
+            Temperature::Warm => "temperature-warm",
+            Temperature::Cool => "temperature-cool",
+            Temperature::Cold => "temperature-cold",
+        }
+    }
+}
+
+/// Enum to represent whether a guess got closer to the secret point than the previous one.
+///
+/// # Variants
+///
+/// - `Warmer`: The guess is closer than the previous one.
+/// - `Colder`: The guess is further away than the previous one.
+/// - `Same`: Both guesses are equally far away.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trend {
+    Warmer,
+    Colder,
+    Same,
+}
+
+impl Trend {
+    /// Returns the catalog key of the message for this trend.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Trend::Warmer => "trend-warmer",
+            Trend::Colder => "trend-colder",
+            Trend::Same => "trend-same",
+        }
+    }
+}
+
+/// Enum to represent the feedback for a guessed point.
+///
+/// # Variants
+///
+/// - `Correct`: The guess is the secret point.
+/// - `Axes(Vec<GuessResult>)`: The result for each coordinate, in `FeedbackMode::Axes`.
+/// - `Distance { temperature, trend }`: How close the guess is, and whether it is closer than
+///   the previous guess if there was one, in `FeedbackMode::Distance`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PointFeedback {
+    Correct,
+    Axes(Vec<GuessResult>),
+    Distance {
+        temperature: Temperature,
+        trend: Option<Trend>,
+    },
+}
+
+/// Enum to represent one line of input from the player in a game played with points.
+///
+/// # Variants
+///
+/// - `Guess(Point)`: The player guessed a point.
+/// - `Command(Command)`: The player typed a command such as `:q`.
+#[derive(Debug, PartialEq)]
+pub enum PointInput {
+    Guess(Point),
+    Command(Command),
+}
+
+/// A struct holding the settings of a game played with points.
+///
+/// # Fields
+/// - `dimensions`: The number of coordinates of the points, 2 or 3.
+/// - `min`, `max`: The smallest and largest value of every coordinate.
+/// - `mode`: How the player is told where the secret point is.
+/// - `rgb`: Whether the points are colors, which can also be typed like `#ff8000`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointGame {
+    dimensions: usize,
+    min: i64,
+    max: i64,
+    mode: FeedbackMode,
+    rgb: bool,
+}
+
+impl Default for PointGame {
+    fn default() -> Self {
+        PointGame::grid(2, Guess::<i64>::MIN, Guess::<i64>::MAX, FeedbackMode::Axes)
+    }
+}
+
+impl PointGame {
+    /// Creates the settings for a game on a grid with `dimensions` axes, each from `min` to
+    /// `max`.
+    ///
+    /// # Panics
+    /// Panics if `dimensions` is not from 1 to `MAX_DIMENSIONS`.
+    pub fn grid(dimensions: usize, min: i64, max: i64, mode: FeedbackMode) -> PointGame {
+        assert!(
+            (1..=MAX_DIMENSIONS).contains(&dimensions),
+            "a point has from 1 to {} dimensions",
+            MAX_DIMENSIONS
+        );
+        PointGame {
+            dimensions,
+            min,
+            max,
+            mode,
+            rgb: false,
+        }
+    }
+
+    /// Creates the settings for a game where the secret is a color, with red, green and blue
+    /// values from 0 to 255.
+    pub fn rgb(mode: FeedbackMode) -> PointGame {
+        PointGame {
+            rgb: true,
+            ..PointGame::grid(3, 0, 255, mode)
+        }
+    }
+
+    /// Returns the number of coordinates of the points.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Returns the smallest and largest value of every coordinate.
+    pub fn range(&self) -> (i64, i64) {
+        (self.min, self.max)
+    }
+
+    /// Returns how the player is told where the secret point is.
+    pub fn mode(&self) -> FeedbackMode {
+        self.mode
+    }
+
+    /// Returns whether the points are colors.
+    pub fn is_rgb(&self) -> bool {
+        self.rgb
+    }
+
+    /// Draws a random secret point.
+    pub fn secret(&self) -> Point {
+        Point::new(
+            (0..self.dimensions)
+                .map(|_| get_secret_number(self.min, self.max))
+                .collect(),
+        )
+    }
+
+    /// Returns a guess the player could type, the middle of the grid, e.g. `50,50`.
+    pub fn example(&self) -> String {
+        let middle = i64::middle(&self.min, &self.max);
+        vec![middle.to_string(); self.dimensions].join(",")
+    }
+
+    /// Parses user input into a point on the grid.
+    ///
+    /// The coordinates are separated by commas, optionally in parentheses, like `12,40` or
+    /// `(12, 40)`. Each coordinate is parsed like a classic guess, so it can also be a number
+    /// in words or a calculation. Colors can also be typed in hex, like `#ff8000`.
+    ///
+    /// # Arguments
+    /// * `input` - The user input to parse.
+    ///
+    /// # Returns
+    /// Returns a `Result<Point, GuessError<i64>>`:
+    /// - `Ok(Point)` if the input is a point on the grid.
+    /// - `Err(GuessError::Empty)` if the input is blank.
+    /// - `Err(GuessError::WrongDimensions)` if the input does not have one coordinate per axis.
+    /// - Any error `Guess::parse_in_range` gives for the first invalid coordinate, such as
+    ///   `GuessError::OutOfRange` for a coordinate off the grid.
+    pub fn parse(&self, input: &str) -> Result<Point, GuessError<i64>> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        if let Some(point) = self.parse_hex(input) {
+            return Ok(point);
+        }
+
+        let inner = input
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .unwrap_or(input);
+        let parts: Vec<&str> = inner.split(',').collect();
+        if parts.len() != self.dimensions || parts.iter().any(|part| part.trim().is_empty()) {
+            return Err(GuessError::WrongDimensions {
+                input: input.to_string(),
+                expected: self.dimensions,
+            });
+        }
+
+        let coords = parts
+            .iter()
+            .map(|part| {
+                Guess::parse_in_range(part, self.min, self.max, None).map(|guess| guess.value())
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Point::new(coords))
+    }
+
+    /// Parses a line typed by the player into a command or a point.
+    ///
+    /// Lines starting with a colon are commands (see `Command::parse`); anything else is parsed
+    /// by `PointGame::parse`.
+    pub fn parse_player_input(&self, line: &str) -> Result<PointInput, GuessError<i64>> {
+        if let Some(command) = Command::parse(line) {
+            return command.map(PointInput::Command);
+        }
+
+        self.parse(line).map(PointInput::Guess)
+    }
+
+    /// Reads a color typed in hex, like `#ff8000`, if this is a color game.
+    fn parse_hex(&self, input: &str) -> Option<Point> {
+        let hex = input.strip_prefix('#').filter(|_| self.rgb)?;
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let coords = (0..6)
+            .step_by(2)
+            .map(|i| i64::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<_>>()?;
+        Some(Point::new(coords))
+    }
+
+    /// Works out the feedback for a guessed point.
+    ///
+    /// # Arguments
+    /// * `guess` - The point the player guessed.
+    /// * `secret` - The secret point.
+    /// * `previous` - The previous guess, which the distance feedback compares against.
+    ///
+    /// # Returns
+    /// Returns `PointFeedback::Correct` if the guess is the secret point, and otherwise the
+    /// part of the `PointComparison` from `handle_guess` that the game's `FeedbackMode` asks
+    /// for.
+    pub fn feedback(
+        &self,
+        guess: &Point,
+        secret: &Point,
+        previous: Option<&Point>,
+    ) -> PointFeedback {
+        let comparison = handle_guess(guess.clone(), secret);
+        if comparison.is_correct() {
+            return PointFeedback::Correct;
+        }
+        match self.mode {
+            FeedbackMode::Axes => PointFeedback::Axes(comparison.axes),
+            FeedbackMode::Distance => {
+                let distance = comparison.distance;
+                let trend =
+                    previous.map(
+                        |previous| match distance.total_cmp(&previous.distance(secret)) {
+                            Ordering::Less => Trend::Warmer,
+                            Ordering::Greater => Trend::Colder,
+                            Ordering::Equal => Trend::Same,
+                        },
+                    );
+                PointFeedback::Distance {
+                    temperature: self.temperature(distance),
+                    trend,
+                }
+            }
+        }
+    }
+
+    /// Returns how hot a guess `distance` away from the secret point is.
+    fn temperature(&self, distance: f64) -> Temperature {
+        let longest = (self.max as f64 - self.min as f64) * (self.dimensions as f64).sqrt();
+        let ratio = if longest > 0.0 {
+            distance / longest
+        } else {
+            0.0
+        };
+        match ratio {
+            r if r <= 0.05 => Temperature::Burning,
+            r if r <= 0.15 => Temperature::Hot,
+            r if r <= 0.30 => Temperature::Warm,
+            r if r <= 0.50 => Temperature::Cool,
+            _ => Temperature::Cold,
+        }
+    }
+}
+
+/// Plays one game with a secret point or color and returns how it ended.
+///
+/// This works like `classic::play`, except that every guess is a point, typed as
+/// coordinates separated by commas, and that the feedback is worked out by
+/// `PointGame::feedback`: either for each axis, or as hot or cold. Only the `:q` command is
+/// available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `game` - The grid and the kind of feedback of the game.
+pub fn play<R: BufRead, W: Write>(prompter: &mut Prompter<R, W>, game: &PointGame) -> GameOutcome {
+    let ui = prompter.ui();
+    let example = game.example();
+    let intro = if game.is_rgb() {
+        ui.format(Role::Info, "color-range", &[("example", &example)])
+    } else {
+        let (min, max) = game.range();
+        let dimensions = game.dimensions();
+        ui.format(
+            Role::Info,
+            "point-range",
+            &[
+                ("dimensions", &dimensions),
+                ("min", &min),
+                ("max", &max),
+                ("example", &example),
+            ],
+        )
+    };
+    prompter.say(&intro);
+
+    let secret = game.secret();
+    let mut guess_count = GuessCount::new();
+    let mut previous: Option<Point> = None;
+
+    let outcome = loop {
+        let input = match prompter.next(|line| game.parse_player_input(line)) {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let guess = match input {
+            PointInput::Guess(guess) => guess,
+            PointInput::Command(Command::Quit) => break GameOutcome::Quit,
+            PointInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+
+        guess_count.increment();
+        match game.feedback(&guess, &secret, previous.as_ref()) {
+            PointFeedback::Correct => {
+                let count = u64::from(guess_count.value());
+                prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                break GameOutcome::Won;
+            }
+            PointFeedback::Axes(results) => {
+                let parts: Vec<String> = results
+                    .iter()
+                    .enumerate()
+                    .map(|(axis, result)| {
+                        let axis = axis_name(ui, game, axis);
+                        let result = match result {
+                            GuessResult::TooSmall => ui.text(Role::TooSmall, "too-small"),
+                            GuessResult::TooBig => ui.text(Role::TooBig, "too-big"),
+                            GuessResult::Correct => ui.text(Role::Correct, "history-correct"),
+                        };
+                        ui.catalog
+                            .format("axis-feedback", &[("axis", &axis), ("result", &result)])
+                    })
+                    .collect();
+                prompter.say(&parts.join(", "));
+            }
+            PointFeedback::Distance { temperature, trend } => {
+                let temperature = ui.catalog.get(temperature.key());
+                let message = match trend {
+                    Some(trend) => ui.format(
+                        Role::Info,
+                        "distance-trend",
+                        &[
+                            ("temperature", &temperature),
+                            ("trend", &ui.catalog.get(trend.key())),
+                        ],
+                    ),
+                    None => ui.theme.paint(Role::Info, &temperature),
+                };
+                prompter.say(&message);
+            }
+        }
+        previous = Some(guess);
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(Role::Headline, "reveal", count, &[("secret", &secret)]));
+    }
+    outcome
+}
+
+/// Returns the name of an axis: `x`, `y` or `z` on a grid, or the color channel of a color.
+fn axis_name(ui: &Ui, game: &PointGame, axis: usize) -> String {
+    if game.is_rgb() {
+        return ui
+            .catalog
+            .get(["axis-red", "axis-green", "axis-blue"][axis]);
+    }
+    ["x", "y", "z"][axis].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test parsing points in the accepted formats
+    #[test]
+    fn parse_points() {
+        let game = PointGame::default();
+        assert_eq!(game.parse("12,40"), Ok(Point::new(vec![12, 40])));
+        assert_eq!(game.parse(" (12, 40) \n"), Ok(Point::new(vec![12, 40])));
+        assert_eq!(game.parse("ten, 2*3"), Ok(Point::new(vec![10, 6])));
+        assert_eq!(game.parse(""), Err(GuessError::Empty));
+        for bad in ["12", "12,40,7", "12,", ",40"] {
+            assert_eq!(
+                game.parse(bad),
+                Err(GuessError::WrongDimensions {
+                    input: bad.to_string(),
+                    expected: 2
+                })
+            );
+        }
+        assert_eq!(
+            game.parse("12,400"),
+            Err(GuessError::OutOfRange {
+                value: 400,
+                min: 1,
+                max: 100
+            })
+        );
+        assert_eq!(Point::parse_input("1,2"), Ok(Point::new(vec![1, 2])));
+        assert_eq!(
+            game.parse_player_input(":q"),
+            Ok(PointInput::Command(Command::Quit))
+        );
+    }
+
+    // Test that colors can be typed in hex
+    #[test]
+    fn parse_colors() {
+        let game = PointGame::rgb(FeedbackMode::Axes);
+        assert_eq!(game.parse("#ff8000"), Ok(Point::new(vec![255, 128, 0])));
+        assert_eq!(game.parse("255,128,0"), Ok(Point::new(vec![255, 128, 0])));
+        assert!(game.parse("#ff80").is_err());
+        assert!(PointGame::default().parse("#ff8000").is_err());
+    }
+
+    // Test the feedback for each axis
+    #[test]
+    fn axes_feedback() {
+        let game = PointGame::grid(3, 0, 10, FeedbackMode::Axes);
+        let secret = Point::new(vec![5, 5, 5]);
+        assert_eq!(
+            game.feedback(&Point::new(vec![1, 9, 5]), &secret, None),
+            PointFeedback::Axes(vec![
+                GuessResult::TooSmall,
+                GuessResult::TooBig,
+                GuessResult::Correct
+            ])
+        );
+        assert_eq!(
+            game.feedback(&secret.clone(), &secret, None),
+            PointFeedback::Correct
+        );
+    }
+
+    // Test that one comparison gives the result for each axis and the distance
+    #[test]
+    fn handle_guess_points() {
+        let secret = Point::new(vec![3, 4]);
+        let comparison = handle_guess(Point::new(vec![0, 9]), &secret);
+        assert_eq!(
+            comparison,
+            PointComparison {
+                axes: vec![GuessResult::TooSmall, GuessResult::TooBig],
+                distance: 34f64.sqrt()
+            }
+        );
+        assert!(!comparison.is_correct());
+        assert!(handle_guess(secret.clone(), &secret).is_correct());
+    }
+
+    // Test the hot and cold feedback
+    #[test]
+    fn distance_feedback() {
+        let game = PointGame::grid(2, 0, 100, FeedbackMode::Distance);
+        let secret = Point::new(vec![50, 50]);
+        let far = Point::new(vec![0, 0]);
+        let near = Point::new(vec![52, 49]);
+        assert_eq!(
+            game.feedback(&far, &secret, None),
+            PointFeedback::Distance {
+                temperature: Temperature::Cool,
+                trend: None
+            }
+        );
+        assert_eq!(
+            game.feedback(&near, &secret, Some(&far)),
+            PointFeedback::Distance {
+                temperature: Temperature::Burning,
+                trend: Some(Trend::Warmer)
+            }
+        );
+        assert_eq!(
+            game.feedback(&far, &secret, Some(&near)),
+            PointFeedback::Distance {
+                temperature: Temperature::Cool,
+                trend: Some(Trend::Colder)
+            }
+        );
+        assert_eq!(
+            Point::new(vec![0, 0]).distance(&Point::new(vec![3, 4])),
+            5.0
+        );
+    }
+
+    // Test that the secret point lies on the grid
+    #[test]
+    fn secret_on_grid() {
+        let game = PointGame::grid(3, -5, 5, FeedbackMode::Axes);
+        for _ in 0..100 {
+            let secret = game.secret();
+            assert_eq!(secret.coords().len(), 3);
+            assert!(secret.coords().iter().all(|c| (-5..=5).contains(c)));
+        }
+        assert_eq!(game.example(), "0,0,0");
+        assert_eq!(Point::new(vec![1, -2]).to_string(), "(1, -2)");
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{theme::Role, ui::Ui, ErrorHandler, GameOutcome, GuessError, MAX_INVALID_INPUTS};
+use std::{
+    fmt,
+    io::{BufRead, Write},
+};
+
+/// A struct that asks the player for input until it gets a valid line.
+///
+/// The `Prompter` struct holds what every game loop needs to talk to the player: the `Ui`,
+/// where input comes from and where output goes. Its `next` method prints the prompt, reads
+/// a line and parses it, reporting every error with `ErrorHandler::handle_error`. It gives up
+/// when the input ends or breaks, or after `MAX_INVALID_INPUTS` invalid lines in a row, so
+/// each game mode only has to deal with valid input. Input can be hidden, so players taking
+/// turns at the same keyboard cannot see each other's guesses.
+///
+/// # Fields
+/// - `ui`: The `Ui` used to translate and style the prompt and the errors.
+/// - `input`: The reader lines are taken from, usually stdin.
+/// - `out`: The writer everything is printed to, usually stdout.
+/// - `invalid_inputs`: The number of invalid lines read in a row.
+/// - `hidden`: Whether typed lines are kept off the screen.
+pub struct Prompter<'a, R, W> {
+    ui: &'a Ui,
+    input: R,
+    out: W,
+    invalid_inputs: u32,
+    hidden: bool,
+}
+
+impl<'a, R: BufRead, W: Write> Prompter<'a, R, W> {
+    /// Creates a prompter reading from `input` and writing to `out`.
+    pub fn new(ui: &'a Ui, input: R, out: W) -> Prompter<'a, R, W> {
+        Prompter {
+            ui,
+            input,
+            out,
+            invalid_inputs: 0,
+            hidden: false,
+        }
+    }
+
+    /// Sets whether typed lines are kept off the screen.
+    ///
+    /// Hiding only works when stdin is a terminal on a Unix system; other input is read as
+    /// usual. While input is hidden, errors do not repeat what was typed either.
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+    }
+
+    /// Returns the `Ui` used for all output.
+    pub fn ui(&self) -> &'a Ui {
+        self.ui
+    }
+
+    /// Prints a line of output, such as the feedback for a guess.
+    ///
+    /// # Panics
+    /// Panics if the output cannot be written, as `println!` does.
+    pub fn say(&mut self, message: &str) {
+        writeln!(self.out, "{}", message).expect("Failed to write output");
+    }
+
+    /// Prompts for a line until `parse` accepts one.
+    ///
+    /// # Arguments
+    /// * `parse` - Turns a line into a value, or explains with a `GuessError` why it cannot.
+    ///
+    /// # Returns
+    /// Returns a `Result`:
+    /// - `Ok(T)` with the value of the first line `parse` accepted.
+    /// - `Err(GameOutcome)` if the game has to end: the input ended or could not be read, or
+    ///   `MAX_INVALID_INPUTS` invalid lines were read in a row. The reason has already been
+    ///   printed.
+    pub fn next<T, V>(
+        &mut self,
+        mut parse: impl FnMut(&str) -> Result<T, GuessError<V>>,
+    ) -> Result<T, GameOutcome>
+    where
+        V: fmt::Display + PartialOrd,
+    {
+        loop {
+            let prompt = self.ui.text(Role::Prompt, "prompt");
+            self.say(&prompt);
+
+            let err = match self.read_line().and_then(|line| parse(&line)) {
+                Ok(value) => {
+                    self.invalid_inputs = 0;
+                    return Ok(value);
+                }
+                Err(err) => err,
+            };
+
+            if self.hidden {
+                self.report_hidden(&err);
+            } else {
+                err.handle_error(self.ui, &mut self.out)
+                    .expect("Failed to write output");
+            }
+
+            // End the game if the input is gone or broken.
+            if let Some(outcome) = GameOutcome::from_error(&err) {
+                return Err(outcome);
+            }
+
+            self.invalid_inputs += 1;
+            if self.invalid_inputs >= MAX_INVALID_INPUTS {
+                let message = self.ui.format(
+                    Role::Error,
+                    "error-too-many-invalid",
+                    &[("count", &self.invalid_inputs)],
+                );
+                self.say(&message);
+                return Err(GameOutcome::TooManyInvalidInputs);
+            }
+        }
+    }
+
+    /// Reports an error in hidden input without repeating what was typed, which would give
+    /// the hidden guess away. Only the range of a guess out of range is named.
+    fn report_hidden<V: fmt::Display + PartialOrd>(&mut self, err: &GuessError<V>) {
+        let message = match err {
+            GuessError::OutOfRange { min, max, .. } => self.ui.format(
+                Role::Error,
+                "error-hidden-range",
+                &[("min", min), ("max", max)],
+            ),
+            GuessError::Empty | GuessError::EndOfInput | GuessError::Io(_) => {
+                err.handle_error(self.ui, &mut self.out)
+                    .expect("Failed to write output");
+                return;
+            }
+            _ => self.ui.text(Role::Error, "error-hidden"),
+        };
+        self.say(&message);
+    }
+
+    /// Reads one line, failing with `GuessError::EndOfInput` when there are none left.
+    fn read_line<V>(&mut self) -> Result<String, GuessError<V>> {
+        let echo = if self.hidden { EchoOff::start() } else { None };
+        let mut line = String::new();
+        let read = self.input.read_line(&mut line);
+        if echo.is_some() {
+            drop(echo);
+            // The Enter key was not echoed either, so end the line it was typed on.
+            writeln!(self.out).expect("Failed to write output");
+        }
+        if read? == 0 {
+            return Err(GuessError::EndOfInput);
+        }
+        Ok(line)
+    }
+}
+
+/// A guard that switches off the echo of the terminal on stdin while it lives.
+///
+/// The echo is switched off with termios, and back on when the guard is dropped. While it is
+/// off, a handler for `SIGINT` switches it back on before the process is interrupted, so
+/// pressing Ctrl-C at a hidden prompt does not leave the terminal without echo.
+///
+/// # Fields
+/// - `saved`: The settings of the terminal before the echo was switched off.
+/// - `previous`: The handler for `SIGINT` before this guard replaced it.
+#[cfg(unix)]
+struct EchoOff {
+    saved: libc::termios,
+    previous: libc::sighandler_t,
+}
+
+/// The settings of the terminal on stdin before the echo was first switched off, for the
+/// `SIGINT` handler to restore.
+#[cfg(unix)]
+static SAVED_TERMIOS: std::sync::OnceLock<libc::termios> = std::sync::OnceLock::new();
+
+#[cfg(unix)]
+impl EchoOff {
+    /// Switches off the echo, returning `None` if stdin is not a terminal.
+    fn start() -> Option<EchoOff> {
+        use std::{io::IsTerminal, mem::MaybeUninit};
+
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        let mut termios = MaybeUninit::<libc::termios>::uninit();
+        // SAFETY: `tcgetattr` fills in `termios` when it succeeds, which is checked before
+        // the value is read.
+        let saved = unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) != 0 {
+                return None;
+            }
+            termios.assume_init()
+        };
+        SAVED_TERMIOS.get_or_init(|| saved);
+
+        let mut hidden = saved;
+        hidden.c_lflag &= !libc::ECHO;
+        let handler = restore_echo as extern "C" fn(libc::c_int);
+        // SAFETY: `hidden` is a valid termios read from the same terminal, and the handler
+        // only calls async-signal-safe functions.
+        unsafe {
+            let previous = libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+            // An ignored `SIGINT` cannot interrupt the read, so it stays ignored.
+            if previous == libc::SIG_IGN {
+                libc::signal(libc::SIGINT, libc::SIG_IGN);
+            }
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &hidden);
+            Some(EchoOff { saved, previous })
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        // SAFETY: `saved` is the valid termios read when the guard was created, and
+        // `previous` is the handler `signal` returned then.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.saved);
+            libc::signal(libc::SIGINT, self.previous);
+        }
+    }
+}
+
+/// Handles `SIGINT` at a hidden prompt: switches the echo back on, then lets the signal
+/// interrupt the process as it would have without the handler.
+#[cfg(unix)]
+extern "C" fn restore_echo(signal: libc::c_int) {
+    // SAFETY: `tcsetattr`, `signal` and `raise` are async-signal-safe, and reading an
+    // initialized `OnceLock` does not allocate or lock.
+    unsafe {
+        if let Some(saved) = SAVED_TERMIOS.get() {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, saved);
+        }
+        libc::signal(signal, libc::SIG_DFL);
+        libc::raise(signal);
+    }
+}
+
+/// Hiding input is not supported outside Unix, so the guard never switches the echo off.
+#[cfg(not(unix))]
+struct EchoOff;
+
+#[cfg(not(unix))]
+impl EchoOff {
+    /// Returns `None`, since the echo cannot be switched off.
+    fn start() -> Option<EchoOff> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{i18n::Locale, theme::Theme, Guess, Parsable};
+    use std::io;
+
+    fn plain_ui() -> Ui {
+        Ui::new(Theme::plain(), Locale::English)
+    }
+
+    // Test that invalid lines are reported and skipped
+    #[test]
+    fn next_skips_invalid_lines() {
+        let ui = plain_ui();
+        let mut out = Vec::new();
+        let mut prompter = Prompter::new(&ui, io::Cursor::new("abc\n42\n"), &mut out);
+        let guess = prompter.next(Guess::<u32>::parse_input);
+        assert_eq!(guess.unwrap().value(), 42);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Please input your guess:\nError: \"abc\" is not a number.\nPlease input your guess:\n"
+        );
+    }
+
+    // Test that errors in hidden input do not repeat what was typed
+    #[test]
+    fn hidden_errors() {
+        let ui = plain_ui();
+        let mut out = Vec::new();
+        let mut prompter = Prompter::new(&ui, io::Cursor::new("150\nabc\n42\n"), &mut out);
+        prompter.set_hidden(true);
+        let guess = prompter.next(Guess::<u32>::parse_input);
+        assert_eq!(guess.unwrap().value(), 42);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Error: that guess is not between 1 and 100."));
+        assert!(out.contains("Error: that is not a valid guess, try again."));
+        assert!(!out.contains("150") && !out.contains("abc"));
+    }
+
+    // Test that the game ends with the input or after too many invalid lines
+    #[test]
+    fn next_gives_up() {
+        let ui = plain_ui();
+        let mut prompter = Prompter::new(&ui, io::Cursor::new(""), io::sink());
+        assert_eq!(
+            prompter.next(Guess::<u32>::parse_input),
+            Err(GameOutcome::EndOfInput)
+        );
+
+        let lines = "x\n".repeat(MAX_INVALID_INPUTS as usize);
+        let mut prompter = Prompter::new(&ui, io::Cursor::new(lines), io::sink());
+        assert_eq!(
+            prompter.next(Guess::<u32>::parse_input),
+            Err(GameOutcome::TooManyInvalidInputs)
+        );
+    }
+}
@@ -0,0 +1,765 @@
+use crate::{
+    command::Command, get_secret_number, handle_query, prompt::Prompter, theme::Role, Answer,
+    GameOutcome, Guess, GuessCount, GuessError, Incrementable, InputKind, Query,
+};
+use std::{
+    fmt,
+    io::{BufRead, Write},
+};
+
+/// The number of questions of a game of twenty questions, unless changed with `--questions`.
+pub const DEFAULT_QUESTIONS: u32 = 20;
+
+/// The largest number of `and`, `or`, `not` and parentheses in a question.
+///
+/// Each of them adds a level to the question, so this bounds how deeply a question nests,
+/// and input like a million `not`s is refused instead of overflowing the stack.
+pub const MAX_NESTING: usize = 256;
+
+/// Enum to represent what a comparison in a question is about.
+///
+/// # Variants
+///
+/// - `Number`: The secret number itself, as in `> 40`.
+/// - `DigitSum`: The sum of the decimal digits of the secret number, as in `digit sum < 10`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Subject {
+    Number,
+    DigitSum,
+}
+
+/// Enum to represent the comparison operators of the query language.
+///
+/// # Variants
+///
+/// - `Less` (`<`), `LessOrEqual` (`<=`), `Greater` (`>`), `GreaterOrEqual` (`>=`),
+///   `Equal` (`=` or `==`) and `NotEqual` (`!=`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    /// Returns the operator as it is written.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Equal => "=",
+            Comparison::NotEqual => "!=",
+        }
+    }
+
+    /// Compares `left` with `right` using this operator.
+    pub fn holds(&self, left: i64, right: i64) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+        }
+    }
+}
+
+/// Enum to represent a yes-or-no question about the secret number in twenty questions.
+///
+/// Predicates are written in a small query language: comparisons like `> 40` or
+/// `digit sum < 10`, the properties `even`, `odd`, `prime` and `square`, `divisible by 7` (or
+/// `multiple of 7`), and any of these combined with `and`, `or`, `not` and parentheses. `not`
+/// binds tightest and `or` loosest, so `even and not prime or > 90` means
+/// `(even and (not prime)) or > 90`. A trailing question mark and the filler words `is` and
+/// `it` are ignored, so `is it prime?` works too.
+///
+/// # Variants
+///
+/// - `Compare { subject, comparison, value }`: Compares the `subject` with `value`.
+/// - `Even`, `Odd`: Whether the number is even or odd.
+/// - `Prime`: Whether the number is a prime number.
+/// - `Square`: Whether the number is a perfect square, such as 0, 1, 4 or 9.
+/// - `DivisibleBy(i64)`: Whether the number is a multiple of a number other than zero.
+/// - `Not(Box<Predicate>)`, `And(..)`, `Or(..)`: The logical combinations of predicates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    Compare {
+        subject: Subject,
+        comparison: Comparison,
+        value: i64,
+    },
+    Even,
+    Odd,
+    Prime,
+    Square,
+    DivisibleBy(i64),
+    Not(Box<Predicate>),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+}
+
+impl Predicate {
+    /// Parses a question written in the query language.
+    ///
+    /// # Returns
+    /// Returns a `Result<Predicate, GuessError<i64>>`:
+    /// - `Ok(Predicate)` with the parsed question.
+    /// - `Err(GuessError::Empty)` if the input is blank.
+    /// - `Err(GuessError::Invalid)` if the input is not in the query language, asks
+    ///   about divisibility by zero, or nests more than `MAX_NESTING` levels deep.
+    pub fn parse(input: &str) -> Result<Predicate, GuessError<i64>> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        let invalid = || GuessError::Invalid {
+            input: input.to_string(),
+            kind: InputKind::Question,
+        };
+        let tokens = tokenize(input.trim_end_matches('?')).ok_or_else(invalid)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            nesting: 0,
+        };
+        let predicate = parser.parse_or().ok_or_else(invalid)?;
+        if parser.pos != parser.tokens.len() {
+            return Err(invalid());
+        }
+        Ok(predicate)
+    }
+
+    /// Checks whether `number` has the property the predicate asks about.
+    pub fn eval(&self, number: i64) -> bool {
+        match self {
+            Predicate::Compare {
+                subject,
+                comparison,
+                value,
+            } => {
+                let left = match subject {
+                    Subject::Number => number,
+                    Subject::DigitSum => digit_sum(number),
+                };
+                comparison.holds(left, *value)
+            }
+            Predicate::Even => number % 2 == 0,
+            Predicate::Odd => number % 2 != 0,
+            Predicate::Prime => is_prime(number),
+            Predicate::Square => {
+                number >= 0 && number.unsigned_abs().isqrt().pow(2) == number as u64
+            }
+            // The only remainder that overflows is `i64::MIN % -1`, which is a multiple.
+            Predicate::DivisibleBy(divisor) => number.checked_rem(*divisor).is_none_or(|r| r == 0),
+            Predicate::Not(inner) => !inner.eval(number),
+            Predicate::And(left, right) => left.eval(number) && right.eval(number),
+            Predicate::Or(left, right) => left.eval(number) || right.eval(number),
+        }
+    }
+}
+
+/// Trait implementation for `Predicate` to ask whether the secret number has a property.
+impl Query<i64> for Predicate {
+    fn matches(&self, secret: &i64) -> bool {
+        self.eval(*secret)
+    }
+}
+
+/// Writes the predicate back in the query language, with parentheses only where they are
+/// needed.
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Writes `inner`, in parentheses if it binds more loosely than its surroundings.
+        let nested = |f: &mut fmt::Formatter<'_>, inner: &Predicate, loose: bool| {
+            if loose {
+                write!(f, "({})", inner)
+            } else {
+                write!(f, "{}", inner)
+            }
+        };
+        match self {
+            Predicate::Compare {
+                subject,
+                comparison,
+                value,
+            } => {
+                if *subject == Subject::DigitSum {
+                    f.write_str("digit sum ")?;
+                }
+                write!(f, "{} {}", comparison.symbol(), value)
+            }
+            Predicate::Even => f.write_str("even"),
+            Predicate::Odd => f.write_str("odd"),
+            Predicate::Prime => f.write_str("prime"),
+            Predicate::Square => f.write_str("square"),
+            Predicate::DivisibleBy(divisor) => write!(f, "divisible by {}", divisor),
+            Predicate::Not(inner) => {
+                f.write_str("not ")?;
+                let loose = matches!(**inner, Predicate::And(..) | Predicate::Or(..));
+                nested(f, inner, loose)
+            }
+            Predicate::And(left, right) => {
+                nested(f, left, matches!(**left, Predicate::Or(..)))?;
+                f.write_str(" and ")?;
+                nested(
+                    f,
+                    right,
+                    matches!(**right, Predicate::Or(..) | Predicate::And(..)),
+                )
+            }
+            Predicate::Or(left, right) => {
+                nested(f, left, false)?;
+                f.write_str(" or ")?;
+                nested(f, right, matches!(**right, Predicate::Or(..)))
+            }
+        }
+    }
+}
+
+/// Returns the sum of the decimal digits of `number`, ignoring its sign.
+fn digit_sum(number: i64) -> i64 {
+    let mut rest = number.unsigned_abs();
+    let mut sum = 0;
+    while rest > 0 {
+        sum += (rest % 10) as i64;
+        rest /= 10;
+    }
+    sum
+}
+
+/// Checks whether `number` is prime, with a Miller-Rabin test whose bases make it exact for
+/// every 64-bit number.
+fn is_prime(number: i64) -> bool {
+    let Ok(n) = u64::try_from(number) else {
+        return false;
+    };
+    if n < 2 {
+        return false;
+    }
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    for base in BASES {
+        if n % base == 0 {
+            return n == base;
+        }
+    }
+    let mul = |a: u64, b: u64| (u128::from(a) * u128::from(b) % u128::from(n)) as u64;
+    let pow = |mut base: u64, mut exp: u64| {
+        let mut result = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul(result, base);
+            }
+            base = mul(base, base);
+            exp >>= 1;
+        }
+        result
+    };
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+    BASES.iter().all(|&base| {
+        let mut x = pow(base, odd);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..shift {
+            x = mul(x, x);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// A token of the query language.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i64),
+    Word(String),
+    Compare(Comparison),
+    Open,
+    Close,
+}
+
+/// Splits a question into tokens, or returns `None` if it has a character the query language
+/// does not use or a number too large for an `i64`.
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        // A minus sign is part of a number unless it could only be a subtraction, which the
+        // language does not have anyway.
+        let negative = c == '-' && input[start + 1..].starts_with(|c: char| c.is_ascii_digit());
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || negative {
+            chars.next();
+            let mut end = start + c.len_utf8();
+            while let Some(&(index, c)) = chars.peek() {
+                if !c.is_ascii_digit() {
+                    break;
+                }
+                end = index + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Number(input[start..end].parse().ok()?));
+        } else if c.is_alphabetic() {
+            let mut end = start;
+            while let Some(&(index, c)) = chars.peek() {
+                if !c.is_alphabetic() {
+                    break;
+                }
+                end = index + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Word(input[start..end].to_lowercase()));
+        } else {
+            chars.next();
+            let next = chars.peek().map(|&(_, c)| c);
+            let (token, pair) = match (c, next) {
+                ('(', _) => (Token::Open, false),
+                (')', _) => (Token::Close, false),
+                ('<', Some('=')) => (Token::Compare(Comparison::LessOrEqual), true),
+                ('>', Some('=')) => (Token::Compare(Comparison::GreaterOrEqual), true),
+                ('=', Some('=')) => (Token::Compare(Comparison::Equal), true),
+                ('!', Some('=')) => (Token::Compare(Comparison::NotEqual), true),
+                ('<', _) => (Token::Compare(Comparison::Less), false),
+                ('>', _) => (Token::Compare(Comparison::Greater), false),
+                ('=', _) => (Token::Compare(Comparison::Equal), false),
+                _ => return None,
+            };
+            if pair {
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+    Some(tokens)
+}
+
+/// A recursive-descent parser for the query language, returning `None` on any syntax error.
+///
+/// # Fields
+/// - `tokens`: The tokens of the question.
+/// - `pos`: The index of the next token to read.
+/// - `nesting`: The number of operators and parentheses read so far, up to `MAX_NESTING`.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    nesting: usize,
+}
+
+impl Parser {
+    /// Counts one more level of nesting, returning `None` once there are too many.
+    fn nest(&mut self) -> Option<()> {
+        self.nesting += 1;
+        (self.nesting <= MAX_NESTING).then_some(())
+    }
+
+    fn peek_word(&self) -> Option<&str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Word(word)) => Some(word),
+            _ => None,
+        }
+    }
+
+    /// Consumes the next token if it is the word `word`.
+    fn eat_word(&mut self, word: &str) -> bool {
+        let found = self.peek_word() == Some(word);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn number(&mut self) -> Option<i64> {
+        match self.next()? {
+            Token::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// or := and ("or" and)*
+    fn parse_or(&mut self) -> Option<Predicate> {
+        let mut left = self.parse_and()?;
+        while self.eat_word("or") {
+            self.nest()?;
+            let right = self.parse_and()?;
+            left = Predicate::Or(Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    /// and := not ("and" not)*
+    fn parse_and(&mut self) -> Option<Predicate> {
+        let mut left = self.parse_not()?;
+        while self.eat_word("and") {
+            self.nest()?;
+            let right = self.parse_not()?;
+            left = Predicate::And(Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    /// not := "not" not | atom, after any filler words
+    fn parse_not(&mut self) -> Option<Predicate> {
+        while self.eat_word("is") || self.eat_word("it") {}
+        if self.eat_word("not") {
+            self.nest()?;
+            return Some(Predicate::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_atom()
+    }
+
+    /// atom := "(" or ")" | comparison | "digit sum" comparison | property
+    fn parse_atom(&mut self) -> Option<Predicate> {
+        match self.next()? {
+            Token::Open => {
+                self.nest()?;
+                let inner = self.parse_or()?;
+                match self.next()? {
+                    Token::Close => Some(inner),
+                    _ => None,
+                }
+            }
+            Token::Compare(comparison) => Some(Predicate::Compare {
+                subject: Subject::Number,
+                comparison,
+                value: self.number()?,
+            }),
+            Token::Word(word) => match word.as_str() {
+                "even" => Some(Predicate::Even),
+                "odd" => Some(Predicate::Odd),
+                "prime" => Some(Predicate::Prime),
+                "square" => Some(Predicate::Square),
+                "divisible" | "multiple" => {
+                    if !(self.eat_word("by") || self.eat_word("of")) {
+                        return None;
+                    }
+                    let divisor = self.number()?;
+                    (divisor != 0).then_some(Predicate::DivisibleBy(divisor))
+                }
+                "digit" if self.eat_word("sum") => match self.next()? {
+                    Token::Compare(comparison) => Some(Predicate::Compare {
+                        subject: Subject::DigitSum,
+                        comparison,
+                        value: self.number()?,
+                    }),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Enum to represent one line of input from the player in twenty questions.
+///
+/// # Variants
+///
+/// - `Question(Predicate)`: The player asked a yes-or-no question.
+/// - `Guess(Guess<i64>)`: The player named the secret number, which ends the game.
+/// - `Command(Command)`: The player typed a command such as `:q`.
+#[derive(Debug, PartialEq)]
+pub enum QuestionInput {
+    Question(Predicate),
+    Guess(Guess<i64>),
+    Command(Command),
+}
+
+/// A struct holding the settings of a game of twenty questions.
+///
+/// # Fields
+/// - `min`, `max`: The range of the secret number.
+/// - `questions`: How many questions the player may ask before naming the number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestionGame {
+    min: i64,
+    max: i64,
+    questions: u32,
+}
+
+impl QuestionGame {
+    /// Creates a game over `min..=max` with a budget of `questions` questions.
+    pub fn new(min: i64, max: i64, questions: u32) -> QuestionGame {
+        QuestionGame {
+            min,
+            max,
+            questions,
+        }
+    }
+
+    /// Returns the smallest and largest possible secret number.
+    pub fn range(&self) -> (i64, i64) {
+        (self.min, self.max)
+    }
+
+    /// Returns how many questions the player may ask.
+    pub fn questions(&self) -> u32 {
+        self.questions
+    }
+
+    /// Parses a line typed by the player into a command, a question or the number they name.
+    ///
+    /// Lines starting with a colon are commands (see `Command::parse`). Lines starting with a
+    /// digit, or a minus sign and a digit, name the number and are parsed by
+    /// `Guess::parse_in_range`. Anything else is a question, parsed by `Predicate::parse`.
+    pub fn parse_player_input(&self, line: &str) -> Result<QuestionInput, GuessError<i64>> {
+        if let Some(command) = Command::parse(line) {
+            return command.map(QuestionInput::Command);
+        }
+        let trimmed = line.trim();
+        let digits = trimmed.strip_prefix('-').unwrap_or(trimmed);
+        if digits.starts_with(|c: char| c.is_ascii_digit()) {
+            return Guess::parse_in_range(trimmed, self.min, self.max, None)
+                .map(QuestionInput::Guess);
+        }
+
+        Predicate::parse(line).map(QuestionInput::Question)
+    }
+}
+
+/// Plays one game of twenty questions and returns how it ended.
+///
+/// The player asks yes-or-no questions about the secret number in the query language of
+/// `Predicate`, each answered by `handle_query` and counted by a `GuessCount` against the
+/// budget of the game. Typing a number names the secret number, which ends the game: it is won
+/// if the number is right and lost otherwise. Once the budget is spent, naming the number is
+/// all that is left. Only the `:q` command is available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `game` - The range and the number of questions.
+pub fn play<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    game: &QuestionGame,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    let (min, max) = game.range();
+    let budget = game.questions();
+    prompter.say(&ui.plural(
+        Role::Info,
+        "questions-intro",
+        u64::from(budget),
+        &[("min", &min), ("max", &max)],
+    ));
+
+    let secret = get_secret_number(min, max);
+    let mut question_count = GuessCount::new();
+
+    let outcome = loop {
+        let input = match prompter.next(|line| game.parse_player_input(line)) {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let question = match input {
+            QuestionInput::Question(question) => question,
+            QuestionInput::Guess(guess) if guess.value() == secret => {
+                let count = u64::from(question_count.value());
+                prompter.say(&ui.plural(Role::Correct, "questions-win", count, &[]));
+                break GameOutcome::Won;
+            }
+            QuestionInput::Guess(guess) => {
+                prompter.say(&ui.format(
+                    Role::Error,
+                    "questions-wrong",
+                    &[("value", &guess.value())],
+                ));
+                break GameOutcome::Lost;
+            }
+            QuestionInput::Command(Command::Quit) => break GameOutcome::Quit,
+            QuestionInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+        if question_count.value() >= budget {
+            prompter.say(&ui.text(Role::Info, "questions-none-left"));
+            continue;
+        }
+
+        question_count.increment();
+        let left = u64::from(budget - question_count.value());
+        let key = match handle_query(&question, &secret) {
+            Answer::Yes => "answer-yes",
+            Answer::No => "answer-no",
+        };
+        prompter.say(&ui.plural(Role::Info, key, left, &[]));
+        if left == 0 {
+            prompter.say(&ui.text(Role::Prompt, "questions-final"));
+        }
+    };
+
+    if outcome != GameOutcome::Won {
+        let count = u64::from(question_count.value());
+        prompter.say(&ui.plural(Role::Headline, "reveal", count, &[("secret", &secret)]));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{handle_query, Answer};
+
+    fn ask(question: &str, number: i64) -> bool {
+        Predicate::parse(question).unwrap().eval(number)
+    }
+
+    // Test the comparisons and properties of the query language
+    #[test]
+    fn simple_questions() {
+        assert!(ask("> 40", 41));
+        assert!(!ask(">40", 40));
+        assert!(ask(">= 40", 40));
+        assert!(ask("< -3", -4));
+        assert!(ask("== 7", 7));
+        assert!(ask("!= 7", 8));
+        assert!(ask("even", 10));
+        assert!(ask("odd", -3));
+        assert!(ask("prime", 97));
+        assert!(!ask("prime", 91));
+        assert!(ask("square", 49));
+        assert!(!ask("square", 50));
+        assert!(ask("divisible by 7", 63));
+        assert!(ask("multiple of 7", -14));
+        assert!(ask("digit sum < 10", 207));
+        assert!(!ask("Digit Sum < 10", 99));
+    }
+
+    // Test combining questions with and, or, not and parentheses
+    #[test]
+    fn combined_questions() {
+        assert!(ask("even and not prime", 4));
+        assert!(!ask("even and not prime", 2));
+        assert!(ask("even and not prime or > 90", 97));
+        assert!(!ask("even and (not prime or > 90)", 97));
+        assert!(ask("not not odd", 3));
+        assert!(ask("is it prime?", 13));
+        assert_eq!(
+            Predicate::parse("even and (not prime or > 90)")
+                .unwrap()
+                .to_string(),
+            "even and (not prime or > 90)"
+        );
+        assert_eq!(
+            Predicate::parse("((even)) or not (odd and > 3)")
+                .unwrap()
+                .to_string(),
+            "even or not (odd and > 3)"
+        );
+    }
+
+    // Test that questions outside the language are rejected
+    #[test]
+    fn invalid_questions() {
+        for input in [
+            "bigger than 40",
+            "> forty",
+            "even and",
+            "(odd",
+            "divisible by 0",
+            "digit sum",
+            "> 99999999999999999999",
+            "even % 3",
+        ] {
+            assert_eq!(
+                Predicate::parse(input),
+                Err(GuessError::Invalid {
+                    input: input.to_string(),
+                    kind: InputKind::Question
+                }),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(Predicate::parse(" "), Err(GuessError::Empty));
+    }
+
+    // Test that deeply nested questions are refused instead of overflowing the stack
+    #[test]
+    fn deeply_nested_questions() {
+        for input in [
+            format!("{}even", "not ".repeat(1_000_000)),
+            format!("{}even{}", "(".repeat(100_000), ")".repeat(100_000)),
+            format!("even{}", " and even".repeat(100_000)),
+        ] {
+            assert!(matches!(
+                Predicate::parse(&input),
+                Err(GuessError::Invalid { .. })
+            ));
+        }
+        let nested = format!("{}even", "not ".repeat(MAX_NESTING));
+        assert!(Predicate::parse(&nested).is_ok());
+    }
+
+    // Test primality against a sieve, and for some large numbers
+    #[test]
+    fn primes() {
+        let mut sieve = vec![true; 10_000];
+        sieve[0] = false;
+        sieve[1] = false;
+        for i in 2..100 {
+            if sieve[i] {
+                for j in (i * i..10_000).step_by(i) {
+                    sieve[j] = false;
+                }
+            }
+        }
+        for (n, &prime) in sieve.iter().enumerate() {
+            assert_eq!(is_prime(n as i64), prime, "{}", n);
+        }
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(i64::MAX - 24));
+        assert!(!is_prime(i64::MAX));
+        assert!(!is_prime(-7));
+        assert!(ask("divisible by -1", i64::MIN));
+    }
+
+    // Test telling questions, numbers and commands apart
+    #[test]
+    fn parse_player_input() {
+        let game = QuestionGame::new(1, 100, DEFAULT_QUESTIONS);
+        assert_eq!(
+            game.parse_player_input("42"),
+            Ok(QuestionInput::Guess(Guess::in_range(42, 1, 100).unwrap()))
+        );
+        assert_eq!(
+            game.parse_player_input("> 42"),
+            Ok(QuestionInput::Question(Predicate::Compare {
+                subject: Subject::Number,
+                comparison: Comparison::Greater,
+                value: 42
+            }))
+        );
+        assert_eq!(
+            game.parse_player_input("-5"),
+            Err(GuessError::OutOfRange {
+                value: -5,
+                min: 1,
+                max: 100
+            })
+        );
+        assert_eq!(
+            game.parse_player_input(":q"),
+            Ok(QuestionInput::Command(Command::Quit))
+        );
+        let question = Predicate::parse("prime").unwrap();
+        assert_eq!(handle_query(&question, &37), Answer::Yes);
+    }
+}
@@ -0,0 +1,354 @@
+use crate::{
+    command::Command, get_secret_number, handle_guess, integer::Integer, parse_player_input,
+    prompt::Prompter, theme::Role, GameOutcome, Guess, GuessCount, GuessResult, Incrementable,
+    PlayerInput,
+};
+use num_bigint::BigUint;
+use std::{
+    collections::BTreeSet,
+    io::{BufRead, Write},
+};
+
+/// The largest number of secrets that can be hidden at once.
+pub const MAX_SECRETS: usize = 100;
+
+/// Enum to represent which hidden secrets a guess gets feedback about.
+///
+/// # Variants
+///
+/// - `Each`: The guess is compared with every secret still hidden.
+/// - `Nearest`: The guess is only compared with the hidden secret closest to it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SecretsFeedback {
+    #[default]
+    Each,
+    Nearest,
+}
+
+/// Enum to represent the feedback for a guess when several secrets are hidden.
+///
+/// # Variants
+///
+/// - `Found { guesses }`: The guess found a secret, after `guesses` guesses since the previous
+///   find. The secret is no longer hidden.
+/// - `Each(Vec<GuessResult>)`: The result against every hidden secret, from the smallest
+///   secret to the largest.
+/// - `Nearest(GuessResult)`: The result against the hidden secret closest to the guess.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecretsResult {
+    Found { guesses: u32 },
+    Each(Vec<GuessResult>),
+    Nearest(GuessResult),
+}
+
+/// A struct holding several secret numbers that are found one at a time.
+///
+/// The `Secrets` struct is the engine of the game with several secrets: every guess is
+/// compared with the secrets still hidden, and a guess equal to one of them finds it and
+/// takes it out of the game. Each find keeps its own `GuessCount`, so the player can see how
+/// many guesses each secret took.
+///
+/// # Fields
+/// - `hidden`: The secrets not found yet, from the smallest to the largest.
+/// - `found`: The secrets found so far, in the order they were found, with the number of
+///   guesses each one took.
+/// - `feedback`: Which hidden secrets a guess gets feedback about.
+/// - `current`: The guesses made since the last find.
+#[derive(Debug)]
+pub struct Secrets<T = u32> {
+    hidden: Vec<Guess<T>>,
+    found: Vec<(T, u32)>,
+    feedback: SecretsFeedback,
+    current: GuessCount,
+}
+
+impl<T: Integer> Secrets<T> {
+    /// Hides `count` different random secrets in the range `min..=max`.
+    ///
+    /// # Panics
+    /// Panics if the range holds fewer than `count` numbers, since the secrets would never
+    /// all be different.
+    pub fn random(count: usize, min: T, max: T, feedback: SecretsFeedback) -> Secrets<T> {
+        assert!(
+            T::distance(&min, &max) + 1u32 >= count.into(),
+            "the range is too small for {} different secrets",
+            count
+        );
+        let mut values: BTreeSet<T> = BTreeSet::new();
+        while values.len() < count {
+            values.insert(get_secret_number(min.clone(), max.clone()));
+        }
+        let secrets = values
+            .into_iter()
+            .map(|value| Guess::in_range(value, min.clone(), max.clone()))
+            .collect::<Result<_, _>>()
+            .expect("Failed to generate secret numbers");
+        Secrets::new(secrets, feedback)
+    }
+
+    /// Hides the given secrets.
+    pub fn new(mut secrets: Vec<Guess<T>>, feedback: SecretsFeedback) -> Secrets<T> {
+        secrets.sort_by_key(Guess::value);
+        secrets.dedup_by(|a, b| a.value() == b.value());
+        Secrets {
+            hidden: secrets,
+            found: Vec::new(),
+            feedback,
+            current: GuessCount::new(),
+        }
+    }
+
+    /// Returns the values of the secrets not found yet, from the smallest to the largest.
+    pub fn hidden(&self) -> Vec<T> {
+        self.hidden.iter().map(Guess::value).collect()
+    }
+
+    /// Returns the secrets found so far, in the order they were found, with the number of
+    /// guesses each one took.
+    pub fn found(&self) -> &[(T, u32)] {
+        &self.found
+    }
+
+    /// Returns `true` once every secret has been found.
+    pub fn all_found(&self) -> bool {
+        self.hidden.is_empty()
+    }
+
+    /// Makes a guess and returns its feedback.
+    ///
+    /// The guess counts towards the current find. If it equals a hidden secret, that secret
+    /// is found and a new count starts for the next one.
+    ///
+    /// # Panics
+    /// Panics if every secret has already been found.
+    pub fn guess(&mut self, guess: Guess<T>) -> SecretsResult {
+        assert!(!self.all_found(), "every secret has already been found");
+        self.current.increment();
+
+        let value = guess.value();
+        if let Some(index) = self
+            .hidden
+            .iter()
+            .position(|secret| secret.value() == value)
+        {
+            self.hidden.remove(index);
+            let guesses = self.current.value();
+            self.found.push((value, guesses));
+            self.current = GuessCount::new();
+            return SecretsResult::Found { guesses };
+        }
+
+        match self.feedback {
+            SecretsFeedback::Each => SecretsResult::Each(
+                self.hidden
+                    .iter()
+                    .map(|secret| handle_guess(guess.clone(), secret))
+                    .collect(),
+            ),
+            SecretsFeedback::Nearest => {
+                let nearest = self
+                    .hidden
+                    .iter()
+                    .min_by_key(|secret| distance(&value, &secret.value()))
+                    .expect("a secret is still hidden");
+                SecretsResult::Nearest(handle_guess(guess, nearest))
+            }
+        }
+    }
+}
+
+/// Returns how far apart two values are.
+fn distance<T: Integer>(a: &T, b: &T) -> BigUint {
+    if a <= b {
+        T::distance(a, b)
+    } else {
+        T::distance(b, a)
+    }
+}
+
+/// Plays one game with several secret numbers and returns how it ended.
+///
+/// This works like `classic::play`, except that `count` different secret numbers are
+/// hidden, and each guess is compared with all of them, or only with the nearest one with
+/// `--nearest`. A guess equal to a secret finds it, and the game is won once every secret
+/// is found. Only the `:q` command is available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `feedback` - Which hidden secrets a guess gets feedback about.
+/// * `count` - The number of secrets to hide, which fits in the range.
+/// * `min`, `max` - The smallest and largest possible secret number.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    feedback: SecretsFeedback,
+    count: usize,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    prompter.say(&ui.format(
+        Role::Info,
+        "secrets-intro",
+        &[("count", &count), ("min", &min), ("max", &max)],
+    ));
+
+    let mut secrets = Secrets::random(count, min.clone(), max.clone(), feedback);
+    let mut guess_count = GuessCount::new();
+
+    let outcome = loop {
+        let input =
+            match prompter.next(|line| parse_player_input(line, min.clone(), max.clone(), None)) {
+                Ok(input) => input,
+                Err(outcome) => break outcome,
+            };
+
+        let guess = match input {
+            PlayerInput::Guess(guess) => guess,
+            PlayerInput::Command(Command::Quit) => break GameOutcome::Quit,
+            PlayerInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+        let value = guess.value();
+
+        guess_count.increment();
+        match secrets.guess(guess) {
+            SecretsResult::Found { guesses } => {
+                prompter.say(&ui.plural(
+                    Role::Correct,
+                    "found-secret",
+                    u64::from(guesses),
+                    &[("value", &value)],
+                ));
+                if secrets.all_found() {
+                    let count = u64::from(guess_count.value());
+                    prompter.say(&ui.plural(Role::Correct, "win", count, &[]));
+                    break GameOutcome::Won;
+                }
+                let left = secrets.hidden().len() as u64;
+                prompter.say(&ui.plural(Role::Info, "secrets-left", left, &[]));
+            }
+            SecretsResult::Each(results) => {
+                let parts: Vec<String> = results
+                    .iter()
+                    .enumerate()
+                    .map(|(index, result)| {
+                        let index = index + 1;
+                        let result = ui.result_text(*result);
+                        ui.catalog
+                            .format("secret-feedback", &[("index", &index), ("result", &result)])
+                    })
+                    .collect();
+                prompter.say(&parts.join(", "));
+            }
+            SecretsResult::Nearest(result) => {
+                let result = ui.result_text(result);
+                prompter.say(
+                    &ui.catalog
+                        .format("nearest-feedback", &[("result", &result)]),
+                );
+            }
+        }
+    };
+
+    if outcome != GameOutcome::Won {
+        let hidden: Vec<String> = secrets.hidden().iter().map(T::to_string).collect();
+        let hidden = hidden.join(", ");
+        let count = u64::from(guess_count.value());
+        prompter.say(&ui.plural(
+            Role::Headline,
+            "reveal-secrets",
+            count,
+            &[("secrets", &hidden)],
+        ));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secrets(values: &[i64], feedback: SecretsFeedback) -> Secrets<i64> {
+        let secrets = values
+            .iter()
+            .map(|&value| Guess::in_range(value, 1, 100).unwrap())
+            .collect();
+        Secrets::new(secrets, feedback)
+    }
+
+    fn guess(value: i64) -> Guess<i64> {
+        Guess::in_range(value, 1, 100).unwrap()
+    }
+
+    // Test that a guess is compared with every hidden secret
+    #[test]
+    fn feedback_for_each_secret() {
+        let mut secrets = secrets(&[70, 20, 45], SecretsFeedback::Each);
+        assert_eq!(secrets.hidden(), [20, 45, 70]);
+        assert_eq!(
+            secrets.guess(guess(50)),
+            SecretsResult::Each(vec![
+                GuessResult::TooBig,
+                GuessResult::TooBig,
+                GuessResult::TooSmall
+            ])
+        );
+    }
+
+    // Test that only the nearest secret is compared, ties going to the smaller one
+    #[test]
+    fn feedback_for_nearest_secret() {
+        let mut secrets = secrets(&[20, 60], SecretsFeedback::Nearest);
+        assert_eq!(
+            secrets.guess(guess(50)),
+            SecretsResult::Nearest(GuessResult::TooSmall)
+        );
+        assert_eq!(
+            secrets.guess(guess(40)),
+            SecretsResult::Nearest(GuessResult::TooBig)
+        );
+        assert_eq!(
+            secrets.guess(guess(25)),
+            SecretsResult::Nearest(GuessResult::TooBig)
+        );
+    }
+
+    // Test that finding a secret removes it and counts its guesses
+    #[test]
+    fn finding_secrets() {
+        let mut secrets = secrets(&[20, 60], SecretsFeedback::Each);
+        secrets.guess(guess(50));
+        assert_eq!(
+            secrets.guess(guess(60)),
+            SecretsResult::Found { guesses: 2 }
+        );
+        assert_eq!(secrets.hidden(), [20]);
+        assert_eq!(
+            secrets.guess(guess(60)),
+            SecretsResult::Each(vec![GuessResult::TooBig])
+        );
+        assert!(!secrets.all_found());
+        assert_eq!(
+            secrets.guess(guess(20)),
+            SecretsResult::Found { guesses: 2 }
+        );
+        assert!(secrets.all_found());
+        assert_eq!(secrets.found(), [(60, 2), (20, 2)]);
+    }
+
+    // Test that random secrets are different and in range
+    #[test]
+    fn random_secrets() {
+        let secrets = Secrets::random(5, 1u8, 5, SecretsFeedback::Each);
+        assert_eq!(secrets.hidden(), [1, 2, 3, 4, 5]);
+        let secrets = Secrets::random(3, -10i64, 10, SecretsFeedback::Nearest);
+        assert_eq!(secrets.hidden().len(), 3);
+        assert!(secrets
+            .hidden()
+            .iter()
+            .all(|value| (-10..=10).contains(value)));
+    }
+}
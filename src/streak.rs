@@ -0,0 +1,231 @@
+use crate::{
+    command::Command, get_secret_number, integer::Integer, prompt::Prompter, theme::Role,
+    GameOutcome, Guess, GuessCount, GuessError, GuessResult, Guessable, Incrementable, InputKind,
+};
+use std::io::{BufRead, Write};
+
+/// Enum to represent the player's call on the next number in the higher-or-lower game.
+///
+/// # Variants
+///
+/// - `Higher`: The next number will be higher than the one shown, typed as `>`, `h` or
+///   `higher`.
+/// - `Lower`: The next number will be lower than the one shown, typed as `<`, `l` or `lower`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Call {
+    Higher,
+    Lower,
+}
+
+impl Call {
+    /// Parses a call, ignoring case and surrounding whitespace, returning `None` if the input
+    /// is not one.
+    pub fn parse(input: &str) -> Option<Call> {
+        match input.trim().to_lowercase().as_str() {
+            ">" | "h" | "higher" => Some(Call::Higher),
+            "<" | "l" | "lower" => Some(Call::Lower),
+            _ => None,
+        }
+    }
+
+    /// Judges the call once the next number is revealed.
+    ///
+    /// The numbers are compared with `Guessable::compare`, so any guessable secret with a
+    /// `GuessResult` as its feedback can be called.
+    ///
+    /// # Arguments
+    /// * `current` - The number shown when the call was made.
+    /// * `next` - The number revealed after it.
+    ///
+    /// # Returns
+    /// Returns `Some(true)` if the call was right, `Some(false)` if it was wrong, and `None`
+    /// if both numbers are the same, so neither call could be right.
+    pub fn judge<G>(self, current: &G, next: &G) -> Option<bool>
+    where
+        G: Guessable<Feedback = GuessResult>,
+    {
+        match (self, next.compare(current)) {
+            (_, GuessResult::Correct) => None,
+            (Call::Higher, result) => Some(result == GuessResult::TooBig),
+            (Call::Lower, result) => Some(result == GuessResult::TooSmall),
+        }
+    }
+}
+
+/// Enum to represent one line of input from the player in the higher-or-lower game.
+///
+/// # Variants
+///
+/// - `Call(Call)`: The player called the next number higher or lower.
+/// - `Command(Command)`: The player typed a command such as `:q`.
+#[derive(Debug, PartialEq)]
+pub enum StreakInput {
+    Call(Call),
+    Command(Command),
+}
+
+/// Parses a line typed by the player into a command or a call.
+///
+/// Lines starting with a colon are commands (see `Command::parse`), and anything else is
+/// parsed by `Call::parse`.
+///
+/// # Returns
+/// Returns a `Result<StreakInput, GuessError<T>>`:
+/// - `Ok(StreakInput)` with the command or the call.
+/// - `Err(GuessError::Empty)` if the line is blank.
+/// - `Err(GuessError::Invalid)` if the line is neither a command nor a call.
+/// - `Err(GuessError::UnknownCommand)` if the line names no command.
+pub fn parse_streak_input<T>(line: &str) -> Result<StreakInput, GuessError<T>> {
+    if let Some(command) = Command::parse(line) {
+        return command.map(StreakInput::Command);
+    }
+    let input = line.trim();
+    if input.is_empty() {
+        return Err(GuessError::Empty);
+    }
+
+    Call::parse(input)
+        .map(StreakInput::Call)
+        .ok_or_else(|| GuessError::Invalid {
+            input: input.to_string(),
+            kind: InputKind::Call,
+        })
+}
+
+/// Plays one game of higher or lower and returns how it ended.
+///
+/// A random number from `get_secret_number` is shown, and the player calls whether the next
+/// one will be higher or lower. Each right call adds one to the streak, counted with a
+/// `GuessCount`, and the same number again neither adds to it nor ends it. The first wrong
+/// call is how every game ends, so it ends as `GameOutcome::Won`. The length of the streak is
+/// the score, shown however the game ends. Only the `:q` command is available.
+///
+/// # Arguments
+/// * `prompter` - Reads the player's input and prints all output, translated and styled by
+///   its `Ui`.
+/// * `min`, `max` - The smallest and largest number that can be drawn.
+pub fn play<T: Integer, R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    min: T,
+    max: T,
+) -> GameOutcome {
+    let ui = prompter.ui();
+    prompter.say(&ui.text(Role::Info, "streak-intro"));
+
+    let draw = || {
+        Guess::in_range(
+            get_secret_number(min.clone(), max.clone()),
+            min.clone(),
+            max.clone(),
+        )
+        .expect("Failed to generate secret number")
+    };
+    let mut current = draw();
+    // Count the right calls in a row.
+    let mut streak = GuessCount::new();
+    prompter.say(&ui.format(
+        Role::Headline,
+        "streak-first",
+        &[("value", &current.value())],
+    ));
+
+    let outcome = loop {
+        let input = match prompter.next(parse_streak_input::<T>) {
+            Ok(input) => input,
+            Err(outcome) => break outcome,
+        };
+
+        let call = match input {
+            StreakInput::Call(call) => call,
+            StreakInput::Command(Command::Quit) => break GameOutcome::Quit,
+            StreakInput::Command(_) => {
+                prompter.say(&ui.text(Role::Info, "command-unavailable"));
+                continue;
+            }
+        };
+
+        let next = draw();
+        let value = next.value();
+        match call.judge(&current, &next) {
+            Some(true) => {
+                streak.increment();
+                let count = u64::from(streak.value());
+                prompter.say(&ui.plural(
+                    Role::Correct,
+                    "streak-right",
+                    count,
+                    &[("value", &value)],
+                ));
+            }
+            Some(false) => {
+                prompter.say(&ui.format(Role::Error, "streak-wrong", &[("value", &value)]));
+                break GameOutcome::Won;
+            }
+            None => prompter.say(&ui.format(Role::Info, "streak-tie", &[("value", &value)])),
+        }
+        current = next;
+    };
+
+    let count = u64::from(streak.value());
+    prompter.say(&ui.plural(Role::Headline, "streak-score", count, &[]));
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{i18n::Locale, theme::Theme, ui::Ui, Guess};
+    use std::io;
+
+    fn guess(value: i64) -> Guess<i64> {
+        Guess::in_range(value, 1, 100).unwrap()
+    }
+
+    // Test parsing calls and commands
+    #[test]
+    fn parse_calls() {
+        assert_eq!(Call::parse(" > "), Some(Call::Higher));
+        assert_eq!(Call::parse("Higher"), Some(Call::Higher));
+        assert_eq!(Call::parse("l"), Some(Call::Lower));
+        assert_eq!(Call::parse("maybe"), None);
+        assert_eq!(
+            parse_streak_input::<i64>(":q"),
+            Ok(StreakInput::Command(Command::Quit))
+        );
+        assert_eq!(parse_streak_input::<i64>("  "), Err(GuessError::Empty));
+        assert_eq!(
+            parse_streak_input::<i64>("up"),
+            Err(GuessError::Invalid {
+                input: "up".to_string(),
+                kind: InputKind::Call
+            })
+        );
+    }
+
+    // Test judging calls against the next number
+    #[test]
+    fn judge_calls() {
+        assert_eq!(Call::Higher.judge(&guess(40), &guess(70)), Some(true));
+        assert_eq!(Call::Higher.judge(&guess(40), &guess(10)), Some(false));
+        assert_eq!(Call::Lower.judge(&guess(40), &guess(10)), Some(true));
+        assert_eq!(Call::Lower.judge(&guess(40), &guess(70)), Some(false));
+        assert_eq!(Call::Lower.judge(&guess(40), &guess(40)), None);
+    }
+
+    // Test that the score is shown however the game ends
+    #[test]
+    fn play_shows_score() {
+        let ui = Ui::new(Theme::plain(), Locale::English);
+        let mut out = Vec::new();
+        let mut prompter = Prompter::new(&ui, io::Cursor::new(":q\n"), &mut out);
+        assert_eq!(play(&mut prompter, 1i64, 100), GameOutcome::Quit);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with(&format!(
+            "{}\n",
+            ui.plural(Role::Headline, "streak-score", 0, &[])
+        )));
+
+        let mut prompter = Prompter::new(&ui, io::empty(), io::sink());
+        assert_eq!(play(&mut prompter, 1i64, 100), GameOutcome::EndOfInput);
+    }
+}
@@ -0,0 +1,323 @@
+use std::io::{self, IsTerminal};
+
+/// The terminal colors that a `Theme` can use.
+///
+/// Each variant maps onto one of the standard ANSI foreground colors, which every
+/// color-capable terminal supports. The bright variants are used by the high-contrast
+/// theme because they stay readable on both dark and light backgrounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl Color {
+    /// Returns the ANSI SGR code that selects this color as the foreground color.
+    fn code(&self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::BrightRed => 91,
+            Color::BrightGreen => 92,
+            Color::BrightYellow => 93,
+            Color::BrightCyan => 96,
+            Color::BrightWhite => 97,
+        }
+    }
+}
+
+/// A struct describing how a piece of text should look in the terminal.
+///
+/// # Fields
+/// - `color`: The foreground color, or `None` to keep the terminal's default color.
+/// - `bold`: Whether the text should be printed in bold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    color: Option<Color>,
+    bold: bool,
+}
+
+impl Style {
+    /// Creates a style that leaves the text unchanged.
+    pub const fn plain() -> Style {
+        Style {
+            color: None,
+            bold: false,
+        }
+    }
+
+    /// Creates a style that prints the text in the given color.
+    pub const fn color(color: Color) -> Style {
+        Style {
+            color: Some(color),
+            bold: false,
+        }
+    }
+
+    /// Returns a copy of this style with bold enabled.
+    pub const fn bold(self) -> Style {
+        Style { bold: true, ..self }
+    }
+
+    /// Wraps `text` in the ANSI escape sequences for this style.
+    ///
+    /// If the style is plain, the text is returned unchanged so that no stray escape
+    /// sequences end up in the output.
+    fn apply(&self, text: &str) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push(1);
+        }
+        if let Some(color) = self.color {
+            codes.push(color.code());
+        }
+        if codes.is_empty() {
+            return text.to_string();
+        }
+        let codes: Vec<String> = codes.iter().map(|c| c.to_string()).collect();
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+    }
+}
+
+/// Enum to represent the different kinds of messages the game prints.
+///
+/// A `Theme` assigns a `Style` to each role, which keeps the game code free of any
+/// knowledge about colors: it only says *what* it is printing, and the theme decides
+/// how that looks.
+///
+/// # Variants
+///
+/// - `Headline`: Titles such as the "Guess the number" banner.
+/// - `Prompt`: The request for the player's next guess.
+/// - `TooSmall`: Feedback for a guess below the secret number.
+/// - `TooBig`: Feedback for a guess above the secret number.
+/// - `Correct`: The victory message.
+/// - `Error`: Error messages about invalid input.
+/// - `Info`: Output of in-game commands such as `:hint` and `:history`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    Headline,
+    Prompt,
+    TooSmall,
+    TooBig,
+    Correct,
+    Error,
+    Info,
+}
+
+/// A struct holding the style used for every `Role`.
+///
+/// The `Theme` struct decides how each kind of message is rendered in the terminal.
+/// Several built-in themes are available (`classic`, `high_contrast` and `plain`) and
+/// can be looked up by name with `Theme::from_name`. Colors can be switched off on any
+/// theme with `Theme::with_colors(false)`, in which case `paint` returns the text unchanged.
+///
+/// # Fields
+/// - `headline`, `prompt`, `too_small`, `too_big`, `correct`, `error`, `info`: The style for each role.
+/// - `enabled`: Whether styles are applied at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    headline: Style,
+    prompt: Style,
+    too_small: Style,
+    too_big: Style,
+    correct: Style,
+    error: Style,
+    info: Style,
+    enabled: bool,
+}
+
+impl Theme {
+    /// The names accepted by `Theme::from_name`.
+    pub const NAMES: [&'static str; 3] = ["classic", "high-contrast", "plain"];
+
+    /// Creates the default theme: blue for too small, yellow for too big, green for a
+    /// correct guess and red for errors, with bold headlines.
+    pub fn classic() -> Theme {
+        Theme {
+            headline: Style::color(Color::Cyan).bold(),
+            prompt: Style::plain().bold(),
+            too_small: Style::color(Color::Blue),
+            too_big: Style::color(Color::Yellow),
+            correct: Style::color(Color::Green).bold(),
+            error: Style::color(Color::Red),
+            info: Style::color(Color::Magenta),
+            enabled: true,
+        }
+    }
+
+    /// Creates a theme using only bold, bright colors for maximum legibility.
+    pub fn high_contrast() -> Theme {
+        Theme {
+            headline: Style::color(Color::BrightWhite).bold(),
+            prompt: Style::color(Color::BrightWhite).bold(),
+            too_small: Style::color(Color::BrightCyan).bold(),
+            too_big: Style::color(Color::BrightYellow).bold(),
+            correct: Style::color(Color::BrightGreen).bold(),
+            error: Style::color(Color::BrightRed).bold(),
+            info: Style::color(Color::BrightWhite),
+            enabled: true,
+        }
+    }
+
+    /// Creates a theme that never styles any text.
+    pub fn plain() -> Theme {
+        Theme {
+            headline: Style::plain(),
+            prompt: Style::plain(),
+            too_small: Style::plain(),
+            too_big: Style::plain(),
+            correct: Style::plain(),
+            error: Style::plain(),
+            info: Style::plain(),
+            enabled: false,
+        }
+    }
+
+    /// Looks up a built-in theme by name.
+    ///
+    /// # Arguments
+    /// * `name` - One of the names in `Theme::NAMES`, compared case-insensitively.
+    ///
+    /// # Returns
+    /// Returns `Some(Theme)` for a known name and `None` otherwise.
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name.to_ascii_lowercase().as_str() {
+            "classic" => Some(Theme::classic()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "plain" => Some(Theme::plain()),
+            _ => None,
+        }
+    }
+
+    /// Returns this theme with styling switched on or off.
+    ///
+    /// Turning colors off keeps the theme's styles but makes `paint` return text unchanged,
+    /// which is what we want when stdout is not a terminal or `NO_COLOR` is set.
+    pub fn with_colors(mut self, enabled: bool) -> Theme {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Returns the style used for the given role.
+    pub fn style(&self, role: Role) -> Style {
+        match role {
+            Role::Headline => self.headline,
+            Role::Prompt => self.prompt,
+            Role::TooSmall => self.too_small,
+            Role::TooBig => self.too_big,
+            Role::Correct => self.correct,
+            Role::Error => self.error,
+            Role::Info => self.info,
+        }
+    }
+
+    /// Renders `text` in the style assigned to `role`.
+    ///
+    /// # Arguments
+    /// * `role` - The kind of message being printed.
+    /// * `text` - The message itself.
+    ///
+    /// # Returns
+    /// Returns the text wrapped in ANSI escape sequences, or unchanged if colors are disabled.
+    pub fn paint(&self, role: Role, text: &str) -> String {
+        if !self.enabled {
+            return text.to_string();
+        }
+        self.style(role).apply(text)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::classic()
+    }
+}
+
+/// Decides whether colored output should be used.
+///
+/// Colors are disabled when the `NO_COLOR` environment variable is set to a non-empty
+/// value (see <https://no-color.org>) or when the output is not a terminal, for example
+/// when it is piped into a file.
+///
+/// # Arguments
+/// * `no_color` - The value of the `NO_COLOR` environment variable, if set.
+/// * `is_terminal` - Whether the output stream is a terminal.
+pub fn should_colorize(no_color: Option<&str>, is_terminal: bool) -> bool {
+    let no_color = no_color.is_some_and(|value| !value.is_empty());
+    is_terminal && !no_color
+}
+
+/// Checks the environment and stdout to decide whether colored output should be used.
+///
+/// This is `should_colorize` applied to the real `NO_COLOR` variable and stdout.
+pub fn colors_supported() -> bool {
+    let no_color = std::env::var("NO_COLOR").ok();
+    should_colorize(no_color.as_deref(), io::stdout().is_terminal())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that a colored theme wraps text in escape sequences
+    #[test]
+    fn paint_applies_style() {
+        let theme = Theme::classic();
+        let painted = theme.paint(Role::Error, "oops");
+        assert_eq!(painted, "\x1b[31moops\x1b[0m");
+    }
+
+    // Test that bold and color are combined into a single escape sequence
+    #[test]
+    fn paint_combines_bold_and_color() {
+        let theme = Theme::high_contrast();
+        let painted = theme.paint(Role::Correct, "yes");
+        assert_eq!(painted, "\x1b[1;92myes\x1b[0m");
+    }
+
+    // Test that disabling colors leaves the text untouched
+    #[test]
+    fn paint_without_colors_is_plain() {
+        let theme = Theme::classic().with_colors(false);
+        assert_eq!(theme.paint(Role::TooBig, "Too big"), "Too big");
+        assert_eq!(Theme::plain().paint(Role::Headline, "Title"), "Title");
+    }
+
+    // Test that every advertised theme name can be looked up
+    #[test]
+    fn from_name_known_and_unknown() {
+        for name in Theme::NAMES {
+            assert!(Theme::from_name(name).is_some(), "{} should exist", name);
+        }
+        assert_eq!(
+            Theme::from_name("HIGH-CONTRAST"),
+            Some(Theme::high_contrast())
+        );
+        assert_eq!(Theme::from_name("neon"), None);
+    }
+
+    // Test the NO_COLOR and terminal detection rules
+    #[test]
+    fn should_colorize_rules() {
+        assert!(should_colorize(None, true));
+        assert!(should_colorize(Some(""), true));
+        assert!(!should_colorize(Some("1"), true));
+        assert!(!should_colorize(None, false));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    i18n::{Catalog, Locale},
+    theme::{Role, Theme},
+    GuessResult,
+};
+use std::fmt::Display;
+
+/// A struct bundling everything needed to present messages to the player.
+///
+/// The `Ui` struct combines a `Theme`, which decides how messages look, with a `Catalog`,
+/// which decides what they say. Game code asks the `Ui` for a message by key and role and
+/// gets back text that is both translated and styled, ready to print.
+///
+/// # Fields
+/// - `theme`: The theme used to style messages.
+/// - `catalog`: The catalog used to translate messages.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ui {
+    pub theme: Theme,
+    pub catalog: Catalog,
+}
+
+impl Ui {
+    /// Creates a new `Ui` from a theme and a locale.
+    pub fn new(theme: Theme, locale: Locale) -> Ui {
+        Ui {
+            theme,
+            catalog: Catalog::new(locale),
+        }
+    }
+
+    /// Returns the translated message for `key`, styled for `role`.
+    pub fn text(&self, role: Role, key: &str) -> String {
+        self.theme.paint(role, &self.catalog.get(key))
+    }
+
+    /// Returns the translated message for `key` with placeholders filled in, styled for `role`.
+    pub fn format(&self, role: Role, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        self.theme.paint(role, &self.catalog.format(key, args))
+    }
+
+    /// Returns the plural form of the message for `key` matching `count`, styled for `role`.
+    pub fn plural(
+        &self,
+        role: Role,
+        key: &str,
+        count: u64,
+        args: &[(&str, &dyn Display)],
+    ) -> String {
+        self.theme
+            .paint(role, &self.catalog.plural(key, count, args))
+    }
+
+    /// Returns the feedback for one comparison, styled by its result.
+    pub fn result_text(&self, result: GuessResult) -> String {
+        match result {
+            GuessResult::TooSmall => self.text(Role::TooSmall, "too-small"),
+            GuessResult::TooBig => self.text(Role::TooBig, "too-big"),
+            GuessResult::Correct => self.text(Role::Correct, "history-correct"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that messages are translated and then styled
+    #[test]
+    fn text_is_translated_and_styled() {
+        let ui = Ui::new(Theme::classic(), Locale::Spanish);
+        assert_eq!(
+            ui.text(Role::TooBig, "too-big"),
+            "\x1b[33mDemasiado grande\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,572 @@
+use crate::i18n::Locale;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::OnceLock,
+};
+
+/// The largest number that can be written or read as words.
+pub const MAX_WORDS_NUMBER: u32 = 999_999;
+
+/// Enum to represent why a phrase could not be read as a number.
+///
+/// # Variants
+///
+/// - `NotWords`: The phrase contains words that are not number words in the language, so it
+///   is not a number written in that language at all.
+/// - `Ambiguous`: Every word is a number word, but they do not form a single well-formed
+///   number, e.g. "five six" (56? 11?) or "hundred hundred".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordsError {
+    NotWords,
+    Ambiguous,
+}
+
+/// Writes `n` out in words in the given language.
+///
+/// The words follow the usual spelling of each language, e.g. "forty-two" in English,
+/// "cuarenta y dos" in Spanish and "quarante-deux" in French.
+///
+/// # Panics
+/// This function will panic if `n` is greater than `MAX_WORDS_NUMBER`.
+pub fn to_words(n: u32, locale: Locale) -> String {
+    assert!(
+        n <= MAX_WORDS_NUMBER,
+        "{} is too large to write in words",
+        n
+    );
+    match locale {
+        Locale::English => english::full(n),
+        Locale::Spanish => spanish::full(n),
+        Locale::French => french::full(n),
+    }
+}
+
+/// Reads a number written in words in the given language.
+///
+/// The input is case-insensitive, accents are optional, and words may be separated by spaces
+/// or hyphens, so "Forty Two", "forty-two", "veintidos" and "quatre vingt dix" are all read.
+/// The connecting words "and", "y" and "et" may be used or left out.
+///
+/// # Arguments
+/// * `input` - The phrase to read.
+/// * `locale` - The language the phrase is written in.
+///
+/// # Returns
+/// Returns a `Result`:
+/// - `Ok(u32)` with the number if the phrase is a well-formed number.
+/// - `Err(WordsError::NotWords)` if the phrase is not made of number words in this language.
+/// - `Err(WordsError::Ambiguous)` if the words do not form exactly one number.
+pub fn parse_words(input: &str, locale: Locale) -> Result<u32, WordsError> {
+    let tokens = tokenize(input, locale);
+    let table = group_table(locale);
+    let scale = scale_word(locale);
+
+    let known = |token: &String| token == scale || table.words.contains(token.as_str());
+    if tokens.is_empty() || !tokens.iter().all(known) {
+        // A connecting word out of place among number words is a malformed number, not
+        // another word.
+        let (connectors, words): (Vec<&String>, Vec<&String>) =
+            tokens.iter().partition(|token| is_connector(token, locale));
+        if !connectors.is_empty() && !words.is_empty() && words.into_iter().all(known) {
+            return Err(WordsError::Ambiguous);
+        }
+        return Err(WordsError::NotWords);
+    }
+
+    let group = |tokens: &[String]| -> Result<u32, WordsError> {
+        table
+            .groups
+            .get(&tokens.join(" "))
+            .copied()
+            .ok_or(WordsError::Ambiguous)
+    };
+
+    match tokens.iter().position(|token| token == scale) {
+        None => group(&tokens),
+        Some(at) => {
+            let (left, right) = (&tokens[..at], &tokens[at + 1..]);
+            if right.iter().any(|token| token == scale) {
+                return Err(WordsError::Ambiguous);
+            }
+            let thousands = match left {
+                // Spanish and French say "mil" and "mille" for one thousand, English needs "one".
+                [] if locale != Locale::English => 1,
+                _ => group(left)?,
+            };
+            let rest = if right.is_empty() { 0 } else { group(right)? };
+            if thousands == 0 {
+                return Err(WordsError::Ambiguous);
+            }
+            Ok(thousands * 1000 + rest)
+        }
+    }
+}
+
+/// Reads a number written in words in any supported language.
+///
+/// Each language in `Locale::ALL` is tried in turn and the first one that reads the phrase
+/// wins. Number words shared by several languages, such as "six", mean the same number in
+/// all of them, so the order does not matter.
+///
+/// # Returns
+/// Returns a `Result`:
+/// - `Ok(u32)` with the number if some language reads the phrase.
+/// - `Err(WordsError::Ambiguous)` if no language reads it, but in at least one language it
+///   is made only of number words.
+/// - `Err(WordsError::NotWords)` otherwise.
+pub fn parse_words_any(input: &str) -> Result<u32, WordsError> {
+    let mut error = WordsError::NotWords;
+    for locale in Locale::ALL {
+        match parse_words(input, locale) {
+            Ok(n) => return Ok(n),
+            Err(WordsError::Ambiguous) => error = WordsError::Ambiguous,
+            Err(WordsError::NotWords) => {}
+        }
+    }
+    Err(error)
+}
+
+/// The number words of one language, built from `to_words` so reading and writing agree.
+///
+/// # Fields
+/// - `groups`: Every number below 1000, keyed by its normalized words joined by spaces.
+/// - `words`: Every individual word used by those numbers.
+struct GroupTable {
+    groups: HashMap<String, u32>,
+    words: HashSet<String>,
+}
+
+/// Returns the word table for `locale`, building it the first time it is needed.
+fn group_table(locale: Locale) -> &'static GroupTable {
+    static TABLES: [OnceLock<GroupTable>; 3] = [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+    let index = Locale::ALL.iter().position(|l| *l == locale).unwrap_or(0);
+    TABLES[index].get_or_init(|| {
+        let mut groups = HashMap::new();
+        let mut words = HashSet::new();
+        for n in 0..1000 {
+            let tokens = tokenize(&to_words(n, locale), locale);
+            words.extend(tokens.iter().cloned());
+            groups.insert(tokens.join(" "), n);
+        }
+        GroupTable { groups, words }
+    })
+}
+
+/// Returns the word for one thousand, which splits a number into two groups.
+fn scale_word(locale: Locale) -> &'static str {
+    match locale {
+        Locale::English => "thousand",
+        Locale::Spanish => "mil",
+        Locale::French => "mille",
+    }
+}
+
+/// Returns whether `token` is the word that connects number words in `locale`.
+fn is_connector(token: &str, locale: Locale) -> bool {
+    matches!(
+        (locale, token),
+        (Locale::English, "and") | (Locale::Spanish, "y") | (Locale::French, "et")
+    )
+}
+
+/// Splits a phrase into normalized words.
+///
+/// The phrase is lowercased, accents are removed and it is split on whitespace and hyphens.
+/// Connecting words between two other words are dropped, and spelling variants are mapped
+/// onto one form, so that every accepted way of writing a number ends up with the same
+/// words. A connecting word at either end or next to another one is kept, so it is not
+/// read as a number word.
+fn tokenize(input: &str, locale: Locale) -> Vec<String> {
+    let folded: String = input
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'î' | 'ï' => 'i',
+            'ó' | 'ô' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            _ => c,
+        })
+        .collect();
+
+    let raw: Vec<&str> = folded
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|token| !token.is_empty())
+        .collect();
+    let between_words = |at: usize| {
+        at > 0
+            && at + 1 < raw.len()
+            && !is_connector(raw[at - 1], locale)
+            && !is_connector(raw[at + 1], locale)
+    };
+
+    let mut tokens: Vec<String> = raw
+        .iter()
+        .enumerate()
+        .filter(|&(at, token)| !(is_connector(token, locale) && between_words(at)))
+        .map(|(_, token)| match (locale, *token) {
+            (Locale::Spanish, "un") => "uno".to_string(),
+            (Locale::Spanish, "veintiun") => "veintiuno".to_string(),
+            (Locale::French, "vingts") => "vingt".to_string(),
+            (Locale::French, "cents") => "cent".to_string(),
+            _ => token.to_string(),
+        })
+        .collect();
+
+    // "a hundred" and "a thousand" mean "one hundred" and "one thousand".
+    if locale == Locale::English
+        && tokens.first().is_some_and(|t| t == "a")
+        && tokens
+            .get(1)
+            .is_some_and(|t| t == "hundred" || t == "thousand")
+    {
+        tokens[0] = "one".to_string();
+    }
+    tokens
+}
+
+mod english {
+    const SMALL: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+
+    fn below_100(n: u32) -> String {
+        match n {
+            0..=19 => SMALL[n as usize].to_string(),
+            _ if n % 10 == 0 => TENS[(n / 10) as usize].to_string(),
+            _ => format!("{}-{}", TENS[(n / 10) as usize], SMALL[(n % 10) as usize]),
+        }
+    }
+
+    fn below_1000(n: u32) -> String {
+        match (n / 100, n % 100) {
+            (0, rest) => below_100(rest),
+            (hundreds, 0) => format!("{} hundred", SMALL[hundreds as usize]),
+            (hundreds, rest) => format!("{} hundred {}", SMALL[hundreds as usize], below_100(rest)),
+        }
+    }
+
+    pub(super) fn full(n: u32) -> String {
+        match (n / 1000, n % 1000) {
+            (0, rest) => below_1000(rest),
+            (thousands, 0) => format!("{} thousand", below_1000(thousands)),
+            (thousands, rest) => format!("{} thousand {}", below_1000(thousands), below_1000(rest)),
+        }
+    }
+}
+
+mod spanish {
+    const SMALL: [&str; 30] = [
+        "cero",
+        "uno",
+        "dos",
+        "tres",
+        "cuatro",
+        "cinco",
+        "seis",
+        "siete",
+        "ocho",
+        "nueve",
+        "diez",
+        "once",
+        "doce",
+        "trece",
+        "catorce",
+        "quince",
+        "dieciséis",
+        "diecisiete",
+        "dieciocho",
+        "diecinueve",
+        "veinte",
+        "veintiuno",
+        "veintidós",
+        "veintitrés",
+        "veinticuatro",
+        "veinticinco",
+        "veintiséis",
+        "veintisiete",
+        "veintiocho",
+        "veintinueve",
+    ];
+    const TENS: [&str; 10] = [
+        "",
+        "",
+        "",
+        "treinta",
+        "cuarenta",
+        "cincuenta",
+        "sesenta",
+        "setenta",
+        "ochenta",
+        "noventa",
+    ];
+    const HUNDREDS: [&str; 10] = [
+        "",
+        "ciento",
+        "doscientos",
+        "trescientos",
+        "cuatrocientos",
+        "quinientos",
+        "seiscientos",
+        "setecientos",
+        "ochocientos",
+        "novecientos",
+    ];
+
+    fn below_100(n: u32) -> String {
+        match n {
+            0..=29 => SMALL[n as usize].to_string(),
+            _ if n % 10 == 0 => TENS[(n / 10) as usize].to_string(),
+            _ => format!("{} y {}", TENS[(n / 10) as usize], SMALL[(n % 10) as usize]),
+        }
+    }
+
+    fn below_1000(n: u32) -> String {
+        match (n / 100, n % 100) {
+            (0, rest) => below_100(rest),
+            (1, 0) => "cien".to_string(),
+            (hundreds, 0) => HUNDREDS[hundreds as usize].to_string(),
+            (hundreds, rest) => format!("{} {}", HUNDREDS[hundreds as usize], below_100(rest)),
+        }
+    }
+
+    pub(super) fn full(n: u32) -> String {
+        let thousands = match n / 1000 {
+            0 => return below_1000(n),
+            1 => "mil".to_string(),
+            // "uno" is shortened to "un" in front of "mil": "veintiún mil", "treinta y un mil".
+            t => {
+                let words = below_1000(t);
+                let words = match words.strip_suffix("veintiuno") {
+                    Some(head) => format!("{}veintiún", head),
+                    None => match words.strip_suffix("uno") {
+                        Some(head) => format!("{}un", head),
+                        None => words,
+                    },
+                };
+                format!("{} mil", words)
+            }
+        };
+        match n % 1000 {
+            0 => thousands,
+            rest => format!("{} {}", thousands, below_1000(rest)),
+        }
+    }
+}
+
+mod french {
+    const SMALL: [&str; 17] = [
+        "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix",
+        "onze", "douze", "treize", "quatorze", "quinze", "seize",
+    ];
+    const TENS: [&str; 7] = [
+        "",
+        "",
+        "vingt",
+        "trente",
+        "quarante",
+        "cinquante",
+        "soixante",
+    ];
+
+    fn below_100(n: u32) -> String {
+        match n {
+            0..=16 => SMALL[n as usize].to_string(),
+            17..=19 => format!("dix-{}", SMALL[(n - 10) as usize]),
+            20..=69 => match n % 10 {
+                0 => TENS[(n / 10) as usize].to_string(),
+                1 => format!("{} et un", TENS[(n / 10) as usize]),
+                unit => format!("{}-{}", TENS[(n / 10) as usize], SMALL[unit as usize]),
+            },
+            71 => "soixante et onze".to_string(),
+            70..=79 => format!("soixante-{}", below_100(n - 60)),
+            80 => "quatre-vingts".to_string(),
+            _ => format!("quatre-vingt-{}", below_100(n - 80)),
+        }
+    }
+
+    fn below_1000(n: u32) -> String {
+        match (n / 100, n % 100) {
+            (0, rest) => below_100(rest),
+            (1, 0) => "cent".to_string(),
+            (1, rest) => format!("cent {}", below_100(rest)),
+            (hundreds, 0) => format!("{} cents", SMALL[hundreds as usize]),
+            (hundreds, rest) => format!("{} cent {}", SMALL[hundreds as usize], below_100(rest)),
+        }
+    }
+
+    pub(super) fn full(n: u32) -> String {
+        let thousands = match n / 1000 {
+            0 => return below_1000(n),
+            1 => "mille".to_string(),
+            // "vingts" and "cents" lose their plural "s" in front of "mille".
+            t => {
+                let words = below_1000(t);
+                let words = words
+                    .strip_suffix('s')
+                    .filter(|head| head.ends_with("vingt") || head.ends_with("cent"))
+                    .map_or(words.clone(), str::to_string);
+                format!("{} mille", words)
+            }
+        };
+        match n % 1000 {
+            0 => thousands,
+            rest => format!("{} {}", thousands, below_1000(rest)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that every number in the game's range reads back from its words, in every language
+    #[test]
+    fn round_trip_whole_range() {
+        for locale in Locale::ALL {
+            for n in 0..=1000 {
+                let words = to_words(n, locale);
+                assert_eq!(parse_words(&words, locale), Ok(n), "{}", words);
+            }
+        }
+    }
+
+    // Test the round trip on a spread of larger numbers
+    #[test]
+    fn round_trip_thousands() {
+        for locale in Locale::ALL {
+            for n in (1000..=MAX_WORDS_NUMBER).step_by(997) {
+                let words = to_words(n, locale);
+                assert_eq!(parse_words(&words, locale), Ok(n), "{}", words);
+            }
+        }
+    }
+
+    // Test a few spellings in each language
+    #[test]
+    fn spellings() {
+        assert_eq!(to_words(42, Locale::English), "forty-two");
+        assert_eq!(to_words(75, Locale::Spanish), "setenta y cinco");
+        assert_eq!(to_words(71, Locale::French), "soixante et onze");
+        assert_eq!(to_words(80_000, Locale::French), "quatre-vingt mille");
+        assert_eq!(to_words(21_000, Locale::Spanish), "veintiún mil");
+    }
+
+    // Test that common variations are accepted
+    #[test]
+    fn variations() {
+        assert_eq!(parse_words("Forty Two", Locale::English), Ok(42));
+        assert_eq!(parse_words("seventy-five", Locale::English), Ok(75));
+        assert_eq!(
+            parse_words("one hundred and five", Locale::English),
+            Ok(105)
+        );
+        assert_eq!(parse_words("a hundred", Locale::English), Ok(100));
+        assert_eq!(parse_words("veintidos", Locale::Spanish), Ok(22));
+        assert_eq!(parse_words("quatre vingt dix", Locale::French), Ok(90));
+        assert_eq!(parse_words("deux cent", Locale::French), Ok(200));
+    }
+
+    // Test that malformed phrases are ambiguous and other words are not numbers
+    #[test]
+    fn rejected_phrases() {
+        assert_eq!(
+            parse_words("five six", Locale::English),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("hundred hundred", Locale::English),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("twenty twenty", Locale::English),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("thousand", Locale::English),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("one thousand one thousand", Locale::English),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("forty banana", Locale::English),
+            Err(WordsError::NotWords)
+        );
+        assert_eq!(
+            parse_words("and", Locale::English),
+            Err(WordsError::NotWords)
+        );
+    }
+
+    // Test that "a" only stands for one before "hundred" or "thousand"
+    #[test]
+    fn rejected_article() {
+        assert_eq!(parse_words("a", Locale::English), Err(WordsError::NotWords));
+        assert_eq!(parse_words("A", Locale::English), Err(WordsError::NotWords));
+        assert_eq!(
+            parse_words("a seven", Locale::English),
+            Err(WordsError::NotWords)
+        );
+        assert_eq!(parse_words("a thousand", Locale::English), Ok(1000));
+    }
+
+    // Test that a connecting word must stand between two number words
+    #[test]
+    fn rejected_connectors() {
+        assert_eq!(
+            parse_words("one hundred and", Locale::English),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("and seven", Locale::English),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("one hundred and and five", Locale::English),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("cuarenta y", Locale::Spanish),
+            Err(WordsError::Ambiguous)
+        );
+        assert_eq!(
+            parse_words("and banana", Locale::English),
+            Err(WordsError::NotWords)
+        );
+    }
+
+    // Test that any language can be used
+    #[test]
+    fn any_language() {
+        assert_eq!(parse_words_any("seventy-five"), Ok(75));
+        assert_eq!(parse_words_any("setenta y cinco"), Ok(75));
+        assert_eq!(parse_words_any("soixante-quinze"), Ok(75));
+        assert_eq!(parse_words_any("five six"), Err(WordsError::Ambiguous));
+        assert_eq!(parse_words_any("banana"), Err(WordsError::NotWords));
+    }
+}